@@ -0,0 +1,22 @@
+//! Record the version of the compiler building the macros.
+//!
+//! A procedural macro has to be built by the same compiler as the crates
+//! that use it, so this is also the compiler of every crate `build_info`
+//! is used in.
+
+use std::env;
+use std::process::Command;
+
+fn main() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|version| version.trim().to_string())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=SRC_EMBED_RUSTC_VERSION={}", version);
+    println!("cargo:rerun-if-env-changed=RUSTC");
+}
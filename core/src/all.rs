@@ -0,0 +1,89 @@
+//! `src_embed_all!`: embedding every item of a block.
+
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+
+use crate::items::{self, SourceItem};
+use crate::{group, source_literal};
+
+/// The contents of a `src_embed_all!` invocation.
+struct Block {
+    dedup: bool,
+    items: Vec<SourceItem>,
+}
+
+impl Parse for Block {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut dedup = false;
+        for attr in input.call(syn::Attribute::parse_inner)? {
+            match &attr.meta {
+                syn::Meta::Path(path) if path.is_ident("dedup") => dedup = true,
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "unsupported src_embed_all flag",
+                    ))
+                }
+            }
+        }
+        let items = items::parse_items(input)?;
+        Ok(Block { dedup, items })
+    }
+}
+
+/// Source constants already emitted by a block, by value.
+///
+/// Two constants only share a value when they also share their attributes
+/// and keyword, so the alias is available wherever the constant is.
+#[derive(Default)]
+pub struct Dedup {
+    seen: HashMap<(String, String), syn::Ident>,
+}
+
+impl Dedup {
+    /// The value of the source constant `ident` holding `source`: the
+    /// earlier constant with the same value, or else the literal itself.
+    pub fn value(
+        &mut self,
+        ident: &syn::Ident,
+        attrs: &TokenStream,
+        keyword: &TokenStream,
+        source: String,
+    ) -> TokenStream {
+        let key = (format!("{} {}", attrs, keyword), source);
+        if let Some(earlier) = self.seen.get(&key) {
+            return earlier.to_token_stream();
+        }
+        let literal = source_literal(&key.1);
+        self.seen.insert(key, ident.clone());
+        literal.into_token_stream()
+    }
+}
+
+/// Expand `src_embed_all! { ... }`.
+///
+/// Every item is embedded as if annotated with `#[src_embed]`; an item that
+/// is annotated takes its arguments from its own attribute.
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let block: Block = syn::parse2(input)?;
+    let mut dedup = Dedup::default();
+    let mut output = TokenStream::new();
+    for item in block.items {
+        let options = item.options.unwrap_or_default();
+        if options.group_by_kind {
+            output.extend(group::expand(item.tokens)?);
+            continue;
+        }
+        let dedup = block.dedup.then_some(&mut dedup);
+        let (consts, item) = crate::embed(&options, item.tokens, dedup)?;
+        output.extend(quote! {
+            #consts
+
+            #item
+        });
+    }
+    Ok(output)
+}
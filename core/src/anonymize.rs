@@ -0,0 +1,426 @@
+//! `anonymize`: the embedded source with the names the item declares
+//! replaced by generic ones.
+
+use std::collections::HashMap;
+
+use syn::visit::{self, Visit};
+
+use crate::lexer;
+
+/// The generic name each identifier declared within `item` is replaced
+/// with.
+///
+/// Names are given in the order they are declared, with one letter sequence
+/// shared by every kind: `TypeA`, `fn_b`, `field_c`. Identifiers that are
+/// only used, such as standard library types and the keywords, are not
+/// declared by the item and keep their names, as do generic parameters and
+/// lifetimes. The items of a trait impl keep the names of the trait.
+pub fn renames(item: &syn::Item) -> HashMap<String, String> {
+    let mut names = Names::default();
+    names.visit_item(item);
+    let mut count = 0;
+    let mut renames = HashMap::new();
+    for (name, kind) in &names.declared {
+        // Skip generic names the item already uses for something else.
+        let generic = loop {
+            count += 1;
+            let generic = generic_name(*kind, count);
+            if !names
+                .declared
+                .iter()
+                .any(|(declared, _)| *declared == generic)
+            {
+                break generic;
+            }
+        };
+        renames.insert(name.clone(), generic);
+    }
+    renames
+}
+
+/// `source` with every identifier in `renames` replaced.
+///
+/// The source is rewritten as text, so the layout of every capture mode is
+/// kept. Identifiers are replaced as whole words in code, macro bodies and
+/// comments; string and character literals are left as they are. A name
+/// after `.` or `::` is only replaced where it refers to the item's own
+/// declaration, as told by [`is_declared_use`], so `Vec::new()` and
+/// `.len()` keep their names when the item declares a `new` or `len` too.
+/// Such a shared name is then also kept on values other than `self`, as in
+/// `other.len()`.
+pub fn rename(source: &str, renames: &HashMap<String, String>) -> String {
+    let text = source.as_bytes();
+    let mut renamed = String::with_capacity(source.len());
+    let mut i = 0;
+    // The end of the last token, before any comment that follows it.
+    let mut code_end = 0;
+    while i < text.len() {
+        if let Some(end) = lexer::comment_end(text, i) {
+            renamed.push_str(&rename_words(&source[i..end], renames));
+            i = end;
+            continue;
+        } else if let Some(end) = lexer::literal_end(text, i) {
+            renamed.push_str(&source[i..end]);
+            i = end;
+        } else if text[i] == b'\'' {
+            // A lifetime or label, whose name may match a declared one.
+            let end = lexer::ident_end(text, i + 1);
+            renamed.push_str(&source[i..end]);
+            i = end;
+        } else if lexer::is_ident_start(text[i]) {
+            let end = lexer::ident_end(text, i);
+            let word = &source[i..end];
+            match renames.get(word) {
+                Some(name) if is_declared_use(source[..code_end].trim_end(), word, renames) => {
+                    renamed.push_str(name)
+                }
+                _ => renamed.push_str(word),
+            }
+            i = end;
+        } else {
+            let end = i + source[i..].chars().next().map_or(1, char::len_utf8);
+            renamed.push_str(&source[i..end]);
+            i = end;
+        }
+        if !text[i - 1].is_ascii_whitespace() {
+            code_end = i;
+        }
+    }
+    renamed
+}
+
+/// `comment` with every whole word in `renames` replaced.
+fn rename_words(comment: &str, renames: &HashMap<String, String>) -> String {
+    let text = comment.as_bytes();
+    let mut renamed = String::with_capacity(comment.len());
+    let mut start = 0;
+    let mut i = 0;
+    while i < text.len() {
+        if lexer::is_ident_start(text[i]) && (i == 0 || !lexer::is_ident_byte(text[i - 1])) {
+            let end = lexer::ident_end(text, i);
+            if let (Some(name), true) = (
+                renames.get(&comment[i..end]),
+                is_declared_use(&comment[..i], &comment[i..end], renames),
+            ) {
+                renamed.push_str(&comment[start..i]);
+                renamed.push_str(name);
+                start = end;
+            }
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+    renamed.push_str(&comment[start..]);
+    renamed
+}
+
+/// Whether the declared name `word`, written right after `before`, refers to
+/// the declaration.
+///
+/// A name after `.` is a field or method. It is taken as the item's own on
+/// `self`, and on other values unless a standard library type has a method
+/// of that name, so `other.total()` is renamed with `fn total` but
+/// `items.len()` keeps its name next to `fn len`. A name after `::` is
+/// taken as the item's own in a path from a declared name, `Self` or `self`,
+/// such as `Invoice::new`, but not in `Vec::new` or `std::mem::take`. Any
+/// other name is a binding site or a reference to one.
+fn is_declared_use(before: &str, word: &str, renames: &HashMap<String, String>) -> bool {
+    let (prefix, path) = if let Some(prefix) = before.strip_suffix("::") {
+        (prefix, true)
+    } else if let Some(prefix) = before.strip_suffix('.') {
+        // The end of a range, as in `0..len`.
+        if prefix.ends_with('.') {
+            return true;
+        }
+        (prefix, false)
+    } else {
+        return true;
+    };
+    let prefix = prefix.trim_end();
+    let start = prefix
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| c.is_alphanumeric() || c == '_')
+        .last()
+        .map_or(prefix.len(), |(start, _)| start);
+    let owner = &prefix[start..];
+    if path {
+        owner == "self" || owner == "Self" || renames.contains_key(owner)
+    } else {
+        owner == "self" || STD_METHODS.binary_search(&word).is_err()
+    }
+}
+
+/// Methods of common standard library types and traits, sorted, whose
+/// calls keep their names even where the item declares the same name.
+const STD_METHODS: &[&str] = &[
+    "abs",
+    "add",
+    "all",
+    "and_then",
+    "any",
+    "append",
+    "as_bytes",
+    "as_mut",
+    "as_ptr",
+    "as_ref",
+    "as_slice",
+    "as_str",
+    "borrow",
+    "borrow_mut",
+    "bytes",
+    "capacity",
+    "chain",
+    "chars",
+    "checked_add",
+    "checked_sub",
+    "chunks",
+    "clear",
+    "clone",
+    "cloned",
+    "cmp",
+    "collect",
+    "contains",
+    "contains_key",
+    "copied",
+    "count",
+    "cycle",
+    "dedup",
+    "default",
+    "drain",
+    "entry",
+    "enumerate",
+    "eq",
+    "expect",
+    "extend",
+    "filter",
+    "filter_map",
+    "find",
+    "first",
+    "flat_map",
+    "flatten",
+    "fmt",
+    "fold",
+    "for_each",
+    "from",
+    "get",
+    "get_mut",
+    "hash",
+    "insert",
+    "into",
+    "into_iter",
+    "is_empty",
+    "is_none",
+    "is_some",
+    "iter",
+    "iter_mut",
+    "join",
+    "keys",
+    "last",
+    "len",
+    "lines",
+    "lock",
+    "map",
+    "map_err",
+    "max",
+    "max_by_key",
+    "min",
+    "min_by_key",
+    "next",
+    "ok",
+    "ok_or",
+    "parse",
+    "partial_cmp",
+    "peek",
+    "pop",
+    "position",
+    "push",
+    "push_str",
+    "remove",
+    "replace",
+    "retain",
+    "rev",
+    "saturating_sub",
+    "skip",
+    "sort",
+    "sort_by",
+    "sort_by_key",
+    "split",
+    "starts_with",
+    "sum",
+    "take",
+    "to_lowercase",
+    "to_owned",
+    "to_string",
+    "to_uppercase",
+    "to_vec",
+    "trim",
+    "truncate",
+    "try_from",
+    "try_into",
+    "unwrap",
+    "unwrap_or",
+    "unwrap_or_default",
+    "unwrap_or_else",
+    "values",
+    "windows",
+    "with_capacity",
+    "wrapping_add",
+    "write",
+    "zip",
+];
+
+/// The kinds of declared names, by the generic name they get.
+#[derive(Clone, Copy)]
+enum Kind {
+    Type,
+    Variant,
+    Fn,
+    Field,
+    Const,
+    Var,
+    Mod,
+    Macro,
+}
+
+/// Collects the names an item declares, in order.
+#[derive(Default)]
+struct Names {
+    declared: Vec<(String, Kind)>,
+    /// Whether the items being visited implement a trait, whose names they
+    /// keep.
+    in_trait_impl: bool,
+}
+
+impl Names {
+    /// Note `ident` as declared as a `kind`. A name declared twice, or as
+    /// two kinds, keeps the first.
+    fn declare(&mut self, ident: &syn::Ident, kind: Kind) {
+        let name = syn::ext::IdentExt::unraw(ident).to_string();
+        if name != "self"
+            && name != "_"
+            && !self.declared.iter().any(|(declared, _)| *declared == name)
+        {
+            self.declared.push((name, kind));
+        }
+    }
+}
+
+/// The `count`th generic name, of `kind`.
+fn generic_name(kind: Kind, count: usize) -> String {
+    let letters = letters(count);
+    let upper = letters.to_uppercase();
+    match kind {
+        Kind::Type => format!("Type{}", upper),
+        Kind::Variant => format!("Variant{}", upper),
+        Kind::Fn => format!("fn_{}", letters),
+        Kind::Field => format!("field_{}", letters),
+        Kind::Const => format!("CONST_{}", upper),
+        Kind::Var => format!("var_{}", letters),
+        Kind::Mod => format!("mod_{}", letters),
+        Kind::Macro => format!("macro_{}", letters),
+    }
+}
+
+/// `a` to `z` for 1 to 26, then `aa`, `ab` and so on.
+fn letters(mut count: usize) -> String {
+    let mut letters = Vec::new();
+    while count > 0 {
+        count -= 1;
+        letters.push(b'a' + (count % 26) as u8);
+        count /= 26;
+    }
+    letters.iter().rev().map(|&b| b as char).collect()
+}
+
+impl<'ast> Visit<'ast> for Names {
+    fn visit_item(&mut self, item: &'ast syn::Item) {
+        match item {
+            syn::Item::Const(item) => self.declare(&item.ident, Kind::Const),
+            syn::Item::Enum(item) => self.declare(&item.ident, Kind::Type),
+            syn::Item::Fn(item) => self.declare(&item.sig.ident, Kind::Fn),
+            syn::Item::Macro(item) => {
+                if let Some(ident) = &item.ident {
+                    self.declare(ident, Kind::Macro);
+                }
+            }
+            syn::Item::Mod(item) => self.declare(&item.ident, Kind::Mod),
+            syn::Item::Static(item) => self.declare(&item.ident, Kind::Const),
+            syn::Item::Struct(item) => self.declare(&item.ident, Kind::Type),
+            syn::Item::Trait(item) => self.declare(&item.ident, Kind::Type),
+            syn::Item::TraitAlias(item) => self.declare(&item.ident, Kind::Type),
+            syn::Item::Type(item) => self.declare(&item.ident, Kind::Type),
+            syn::Item::Union(item) => self.declare(&item.ident, Kind::Type),
+            _ => {}
+        }
+        visit::visit_item(self, item);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast syn::ItemImpl) {
+        // An inherent impl can only be of a type of this crate; a trait may
+        // be implemented for any type.
+        if item.trait_.is_none() {
+            if let syn::Type::Path(path) = &*item.self_ty {
+                if let Some(segment) = path.path.segments.last() {
+                    self.declare(&segment.ident, Kind::Type);
+                }
+            }
+        }
+        let outer = std::mem::replace(&mut self.in_trait_impl, item.trait_.is_some());
+        visit::visit_item_impl(self, item);
+        self.in_trait_impl = outer;
+    }
+
+    fn visit_impl_item(&mut self, item: &'ast syn::ImplItem) {
+        match item {
+            _ if self.in_trait_impl => {}
+            syn::ImplItem::Const(item) => self.declare(&item.ident, Kind::Const),
+            syn::ImplItem::Fn(item) => self.declare(&item.sig.ident, Kind::Fn),
+            syn::ImplItem::Type(item) => self.declare(&item.ident, Kind::Type),
+            _ => {}
+        }
+        visit::visit_impl_item(self, item);
+    }
+
+    fn visit_trait_item(&mut self, item: &'ast syn::TraitItem) {
+        match item {
+            syn::TraitItem::Const(item) => self.declare(&item.ident, Kind::Const),
+            syn::TraitItem::Fn(item) => self.declare(&item.sig.ident, Kind::Fn),
+            syn::TraitItem::Type(item) => self.declare(&item.ident, Kind::Type),
+            _ => {}
+        }
+        visit::visit_trait_item(self, item);
+    }
+
+    fn visit_foreign_item(&mut self, item: &'ast syn::ForeignItem) {
+        match item {
+            syn::ForeignItem::Fn(item) => self.declare(&item.sig.ident, Kind::Fn),
+            syn::ForeignItem::Static(item) => self.declare(&item.ident, Kind::Const),
+            syn::ForeignItem::Type(item) => self.declare(&item.ident, Kind::Type),
+            _ => {}
+        }
+        visit::visit_foreign_item(self, item);
+    }
+
+    fn visit_variant(&mut self, variant: &'ast syn::Variant) {
+        self.declare(&variant.ident, Kind::Variant);
+        visit::visit_variant(self, variant);
+    }
+
+    fn visit_field(&mut self, field: &'ast syn::Field) {
+        if let Some(ident) = &field.ident {
+            self.declare(ident, Kind::Field);
+        }
+        visit::visit_field(self, field);
+    }
+
+    fn visit_pat_ident(&mut self, pat: &'ast syn::PatIdent) {
+        // A pattern that is a bare uppercase name is a unit variant or a
+        // constant, such as `None`, not a new binding.
+        if !pat.ident.to_string().starts_with(char::is_uppercase) {
+            self.declare(&pat.ident, Kind::Var);
+        }
+        visit::visit_pat_ident(self, pat);
+    }
+}
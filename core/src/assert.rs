@@ -0,0 +1,107 @@
+//! Assertions about embedded sources: `assert_source_eq!` compares one with
+//! a fixture file, `assert_source_len_eq!` the lengths of two at compile
+//! time.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+#[cfg(feature = "test-utils")]
+use syn::LitStr;
+use syn::{Path, Token};
+
+use crate::{naming, Case};
+
+/// The arguments of `assert_source_eq!(path::Item, "fixture")`.
+#[cfg(feature = "test-utils")]
+struct Args {
+    item: Path,
+    fixture: LitStr,
+}
+
+#[cfg(feature = "test-utils")]
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let item = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let fixture = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Args { item, fixture })
+    }
+}
+
+/// Expand `assert_source_eq!(Item, "fixture")`.
+#[cfg(feature = "test-utils")]
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let Args { item, fixture } = syn::parse2(input)?;
+    let source = source_const(item)?;
+    Ok(quote! {
+        ::src_embed::__private::assert_source_eq(
+            #source,
+            ::core::concat!(::core::env!("CARGO_MANIFEST_DIR"), "/", #fixture),
+        )
+    })
+}
+
+/// The arguments of `assert_source_len_eq!(path::A, path::B)`.
+struct LenArgs {
+    left: Path,
+    right: Path,
+}
+
+impl Parse for LenArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let left = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let right = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(LenArgs { left, right })
+    }
+}
+
+/// Expand `assert_source_len_eq!(A, B)` into a `const` item that fails to
+/// evaluate when the two sources differ in length.
+pub fn expand_len(input: TokenStream) -> syn::Result<TokenStream> {
+    let LenArgs { left, right } = syn::parse2(input)?;
+    let message = format!(
+        "the embedded sources of `{}` and `{}` differ in length",
+        display(&left),
+        display(&right)
+    );
+    let (left, right) = (source_const(left)?, source_const(right)?);
+    Ok(quote! {
+        const _: () = ::core::assert!(#left.len() == #right.len(), #message);
+    })
+}
+
+/// The path of the source constant of the item at `item`.
+///
+/// The last segment of the path is the item's name, turned into the name
+/// of its source constant by the default naming rules; the segments before
+/// it are kept, so `shapes::Circle` refers to `shapes::__CIRCLE_SOURCE__`.
+fn source_const(mut item: Path) -> syn::Result<Path> {
+    let Some(last) = item.segments.last_mut() else {
+        return Err(syn::Error::new_spanned(
+            &item,
+            "expected the name of an embedded item",
+        ));
+    };
+    let item_name = naming::apply_case(&last.ident.unraw().to_string(), Case::default());
+    last.ident = syn::Ident::new(&naming::source_const_name(&item_name), last.ident.span());
+    Ok(item)
+}
+
+/// `path` as it would be written, such as `shapes::Circle`.
+fn display(path: &Path) -> String {
+    let segments: Vec<String> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect();
+    let prefix = if path.leading_colon.is_some() {
+        "::"
+    } else {
+        ""
+    };
+    format!("{}{}", prefix, segments.join("::"))
+}
@@ -0,0 +1,40 @@
+//! Locating `#[src_embed]` attributes on parsed items.
+
+use syn::{Attribute, Item};
+
+/// Whether `attr` is `#[src_embed]`, under any path ending in `src_embed`.
+pub fn is_src_embed(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "src_embed")
+}
+
+/// Remove the first `#[src_embed]` attribute of `item` and return it.
+pub fn take_src_embed(item: &mut Item) -> Option<Attribute> {
+    let attrs = item_attrs_mut(item)?;
+    let position = attrs.iter().position(is_src_embed)?;
+    Some(attrs.remove(position))
+}
+
+/// The attributes of `item`, for the item kinds that have any.
+pub fn item_attrs_mut(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    match item {
+        Item::Const(item) => Some(&mut item.attrs),
+        Item::Enum(item) => Some(&mut item.attrs),
+        Item::ExternCrate(item) => Some(&mut item.attrs),
+        Item::Fn(item) => Some(&mut item.attrs),
+        Item::ForeignMod(item) => Some(&mut item.attrs),
+        Item::Impl(item) => Some(&mut item.attrs),
+        Item::Macro(item) => Some(&mut item.attrs),
+        Item::Mod(item) => Some(&mut item.attrs),
+        Item::Static(item) => Some(&mut item.attrs),
+        Item::Struct(item) => Some(&mut item.attrs),
+        Item::Trait(item) => Some(&mut item.attrs),
+        Item::TraitAlias(item) => Some(&mut item.attrs),
+        Item::Type(item) => Some(&mut item.attrs),
+        Item::Union(item) => Some(&mut item.attrs),
+        Item::Use(item) => Some(&mut item.attrs),
+        _ => None,
+    }
+}
@@ -0,0 +1,62 @@
+//! `build_info`: the conditions an item was embedded under.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+use crate::{naming, Options};
+
+/// Generate `__FOO_RUST_EDITION__` and `__FOO_BUILT_WITH__`.
+///
+/// The edition is the `edition = "..."` argument, or else the `edition` key
+/// of the manifest of the crate being compiled, found through
+/// `CARGO_MANIFEST_DIR`. It is empty when neither is known. The compiler is
+/// the one that built the macros, which is always the compiler of the crate
+/// using them.
+pub fn expand(options: &Options, item_name: &str, attrs: &TokenStream) -> TokenStream {
+    let edition = match &options.edition {
+        Some(edition) => edition.value(),
+        None => manifest_edition().unwrap_or_default(),
+    };
+    let built_with = env!("SRC_EMBED_RUSTC_VERSION");
+    let edition_ident = syn::Ident::new(
+        &naming::const_name(item_name, "RUST_EDITION"),
+        Span::call_site(),
+    );
+    let built_with_ident = syn::Ident::new(
+        &naming::const_name(item_name, "BUILT_WITH"),
+        Span::call_site(),
+    );
+    quote! {
+        #attrs
+        pub const #edition_ident: &str = #edition;
+        #attrs
+        pub const #built_with_ident: &str = #built_with;
+    }
+}
+
+/// The edition set in the `[package]` table of the compiling crate's
+/// `Cargo.toml`. An edition inherited from the workspace is not resolved.
+fn manifest_edition() -> Option<String> {
+    let dir = env::var_os("CARGO_MANIFEST_DIR")?;
+    let manifest = fs::read_to_string(Path::new(&dir).join("Cargo.toml")).ok()?;
+    let mut in_package = false;
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if in_package {
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            if key.trim() == "edition" {
+                let value = value.split('#').next().unwrap_or_default().trim();
+                return Some(value.trim_matches(|c| c == '"' || c == '\'').to_string());
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,668 @@
+//! Capturing the text of the annotated item.
+//!
+//! The default capture renders the item's tokens, which loses comments and
+//! the original layout. [`original`] instead recovers the text exactly as it
+//! was written by reading the item's source file at the span locations of
+//! its tokens.
+
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+
+use proc_macro2::{Delimiter, LineColumn, Span, TokenStream, TokenTree};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Meta, Token};
+
+use crate::{attrs, lexer};
+
+/// Render `input` from its tokens.
+///
+/// The compiler writes doc comments back as `///` comments only while they
+/// are still the tokens it lexed. Streams put together from parts of
+/// another one, as `src_embed_all!` does, hold them as plain `#[doc]`
+/// attributes instead, so doc comments are recognised by the text of their
+/// span and written the way the compiler would have.
+pub fn reflow(input: &TokenStream) -> String {
+    let tokens: Vec<TokenTree> = input.clone().into_iter().collect();
+    let mut text = String::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < tokens.len() {
+        let Some((comment, len)) = doc_comment(&tokens[i..]) else {
+            i += 1;
+            continue;
+        };
+        if start < i {
+            text.push_str(
+                &tokens[start..i]
+                    .iter()
+                    .cloned()
+                    .collect::<TokenStream>()
+                    .to_string(),
+            );
+            text.push(' ');
+        }
+        text.push_str(&comment);
+        text.push(if comment.starts_with("//") { '\n' } else { ' ' });
+        i += len;
+        start = i;
+    }
+    let rest: TokenStream = tokens[start..].iter().cloned().collect();
+    if start == 0 {
+        return rest.to_string();
+    }
+    text.push_str(&rest.to_string());
+    text
+}
+
+/// The doc comment that the `#[doc]` attribute at the start of `tokens` was
+/// written as, with the number of tokens it takes up.
+pub fn doc_comment(tokens: &[TokenTree]) -> Option<(String, usize)> {
+    let TokenTree::Punct(hash) = tokens.first()? else {
+        return None;
+    };
+    let len = match tokens.get(1)? {
+        TokenTree::Punct(bang) if bang.as_char() == '!' => 3,
+        _ => 2,
+    };
+    if hash.as_char() != '#' || !matches!(tokens.get(len - 1)?, TokenTree::Group(_)) {
+        return None;
+    }
+    let comment = hash.span().source_text()?;
+    let is_doc = ["///", "//!", "/**", "/*!"]
+        .iter()
+        .any(|start| comment.starts_with(start));
+    is_doc.then_some((comment, len))
+}
+
+/// Recover the text of `input` byte for byte from its source file.
+///
+/// The text runs from the item's first token to its last one. Attributes
+/// found between the item's tokens — the `#[src_embed]` attribute itself,
+/// which the compiler does not pass to the macro — are cut out, while
+/// comments and whitespace are kept. Line endings are `\n`, as the compiler
+/// normalizes them when it reads the file.
+///
+/// Returns `None` when the text cannot be recovered: the tokens have no
+/// backing file (for example when they come from a `macro_rules!`
+/// expansion), the file cannot be read, or the recovered text does not
+/// tokenize back to exactly `input`. Callers fall back to [`reflow`].
+pub fn original(input: &TokenStream) -> Option<String> {
+    let spans: Vec<Span> = input.clone().into_iter().map(|tt| tt.span()).collect();
+    let file = spans.first()?.local_file()?;
+    let text = SourceText::read(&file)?;
+
+    let mut captured = String::new();
+    let mut last_end: Option<LineColumn> = None;
+    for span in spans {
+        let (start, end) = (span.start(), span.end());
+        if let Some(last_end) = last_end {
+            // A doc comment is passed as `#` and `[doc = ...]`, both spanning
+            // the whole comment.
+            if end <= last_end {
+                continue;
+            }
+            if start < last_end {
+                return None;
+            }
+            captured.push_str(&strip_attributes(text.slice(last_end, start)?));
+        }
+        captured.push_str(text.slice(start, end)?);
+        last_end = Some(end);
+    }
+
+    // Anything that went wrong above (a token from another file, a macro
+    // expansion pointing into its definition, ...) shows up as a mismatch.
+    let reparsed: TokenStream = captured.parse().ok()?;
+    same_tokens(&reparsed, input).then_some(captured)
+}
+
+/// The outer attributes of an item as written in its source file.
+///
+/// The compiler evaluates `#[cfg]` and `#[cfg_attr]` attributes before it
+/// expands `#[src_embed]`, and does not pass attributes placed above a
+/// derive-like attribute on to the macro either, so the tokens of an item
+/// can lack attributes that are part of its meaning. These are recovered
+/// from the file instead.
+pub struct Written {
+    /// The text of the attributes up to the rest of the item, with
+    /// `#[src_embed]` cut out.
+    text: String,
+    /// Each attribute or doc comment, in order, without `#[src_embed]`.
+    attrs: Vec<String>,
+    /// The column of the first attribute in its file.
+    column: usize,
+    /// The item without its leading attributes.
+    body: TokenStream,
+}
+
+impl Written {
+    /// The text of the item as written, like [`original`].
+    pub fn original(&self) -> Option<String> {
+        Some(format!("{}{}", self.text, original(&self.body)?))
+    }
+
+    /// The item rendered from its tokens, like [`reflow`], with the written
+    /// attributes.
+    pub fn reflow(&self) -> String {
+        let mut text = String::new();
+        for attr in &self.attrs {
+            if is_doc_comment(attr) {
+                text.push_str(attr);
+                text.push(if attr.starts_with("//") { '\n' } else { ' ' });
+            } else {
+                text.push_str(
+                    &attr
+                        .parse::<TokenStream>()
+                        .map_or_else(|_| attr.clone(), |tokens| tokens.to_string()),
+                );
+                text.push(' ');
+            }
+        }
+        text.push_str(&reflow(&self.body));
+        text
+    }
+
+    /// The tokens of the item with the written attributes.
+    pub fn tokens(&self) -> TokenStream {
+        let mut tokens: TokenStream = self.attrs.join("\n").parse().unwrap_or_default();
+        tokens.extend(self.body.clone());
+        tokens
+    }
+
+    /// The column the first line of [`Written::original`] starts at.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+/// Recover the outer attributes of `input` from its source file.
+///
+/// The attributes run back from the item's first token over every
+/// attribute and doc comment directly above it, which takes in the
+/// `#[src_embed]` attribute and any attribute the compiler removed. Returns
+/// `None` when the tokens have no backing file or when one of the
+/// attributes the item does have lies outside that text, as happens for
+/// items put together by a `macro_rules!` macro.
+pub fn written(input: &TokenStream) -> Option<Written> {
+    let tokens: Vec<TokenTree> = input.clone().into_iter().collect();
+    let mut body_start = 0;
+    while matches!(&tokens[body_start..], [TokenTree::Punct(hash), TokenTree::Group(group), ..]
+        if hash.as_char() == '#' && group.delimiter() == Delimiter::Bracket)
+    {
+        body_start += 2;
+    }
+    let body_token = tokens.get(body_start)?;
+    let file = body_token.span().local_file()?;
+    let text = SourceText::read(&file)?;
+    let end = text.offset(body_token.span().start())?;
+    // Positions that do not match the file, as in doctests, are caught here.
+    if text.slice(body_token.span().start(), body_token.span().end())? != body_token.to_string() {
+        return None;
+    }
+    let first = tokens.first()?.span().start();
+    let start = attributes_start(&text.text, text.offset(first)?.min(end));
+    for tt in &tokens[..body_start] {
+        let (attr_start, attr_end) = (
+            text.offset(tt.span().start())?,
+            text.offset(tt.span().end())?,
+        );
+        if attr_start < start || attr_end > end {
+            return None;
+        }
+    }
+
+    let region = &text.text[start..end];
+    let mut written = Written {
+        text: String::new(),
+        attrs: Vec::new(),
+        column: 0,
+        body: tokens[body_start..].iter().cloned().collect(),
+    };
+    // Where the kept text starts, once something besides whitespace is kept.
+    let mut kept_start = None;
+    let mut rest = region;
+    while let Some(ch) = rest.chars().next() {
+        if kept_start.is_none()
+            && written.text.trim().is_empty()
+            && !ch.is_whitespace()
+            && !is_src_embed_at(rest)
+        {
+            written.text.clear();
+            kept_start = Some(end - rest.len());
+        }
+        let len = if rest.starts_with("//") {
+            rest.find('\n').unwrap_or(rest.len())
+        } else if rest.starts_with("/*") {
+            block_comment_len(rest)
+        } else if ch == '#' {
+            attribute_len(rest)
+        } else {
+            written.text.push(ch);
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        };
+        let (piece, after) = rest.split_at(len);
+        rest = after;
+        if ch != '#' && !is_doc_comment(piece) {
+            written.text.push_str(piece);
+        } else if is_src_embed(piece) {
+            // Drop the line as well when the attribute stands on its own.
+            let line_start = written.text.rfind('\n').map_or(0, |i| i + 1);
+            if written.text[line_start..].trim().is_empty() {
+                if let Some(newline) = rest.find('\n') {
+                    if rest[..newline].trim().is_empty() {
+                        written.text.truncate(line_start);
+                        rest = &rest[newline + 1..];
+                    }
+                }
+            }
+        } else {
+            if ch == '#' && syn::parse_str::<AttributeText>(piece).is_err() {
+                return None;
+            }
+            written.text.push_str(piece);
+            written.attrs.push(piece.to_string());
+        }
+    }
+    if kept_start.is_none() {
+        written.text.clear();
+    }
+    let kept_start = kept_start.unwrap_or(end);
+    written.column = text.text[..kept_start]
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count();
+    Some(written)
+}
+
+/// Whether `text` starts with a `#[src_embed]` attribute.
+fn is_src_embed_at(text: &str) -> bool {
+    text.starts_with('#') && is_src_embed(&text[..attribute_len(text)])
+}
+
+/// The start of the attributes and doc comments that directly precede
+/// byte `end` of `text`.
+fn attributes_start(text: &str, end: usize) -> usize {
+    let mut start = end;
+    let mut pos = end;
+    loop {
+        let before = text[..pos].trim_end();
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let line = before[line_start..].trim_start();
+        let line_offset = before.len() - line.len();
+        if let Some(comment) = line_comment_start(line).filter(|&comment| comment > 0) {
+            // A comment after code on the same line: only a doc comment
+            // belongs to the item, and text in either is not an attribute.
+            if is_doc_comment(&line[comment..]) {
+                start = line_offset + comment;
+            }
+            pos = line_offset + comment;
+        } else if line.starts_with("//") {
+            if is_doc_comment(line) {
+                start = line_offset;
+            }
+            pos = line_offset;
+        } else if before.ends_with("*/") {
+            let Some(open) = before.rfind("/*") else {
+                break;
+            };
+            if is_doc_comment(&before[open..]) {
+                start = open;
+            }
+            pos = open;
+        } else if before.ends_with(']') {
+            let Some(open) = matching_bracket(before) else {
+                break;
+            };
+            let hash = before[..open].trim_end();
+            if !hash.ends_with('#')
+                || attribute_len(&text[hash.len() - 1..]) != before.len() - (hash.len() - 1)
+            {
+                break;
+            }
+            start = hash.len() - 1;
+            pos = start;
+        } else {
+            break;
+        }
+    }
+    start
+}
+
+/// The offset of the `//` comment in `line`, if it has one outside of
+/// literals and block comments.
+fn line_comment_start(line: &str) -> Option<usize> {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"//") {
+            return Some(i);
+        }
+        i = lexer::comment_end(bytes, i)
+            .or_else(|| lexer::literal_end(bytes, i))
+            .unwrap_or(i + 1);
+    }
+    None
+}
+
+/// The offset of the `[` matching the `]` that `text` ends with.
+fn matching_bracket(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, ch) in text.char_indices().rev() {
+        match ch {
+            ']' => depth += 1,
+            '[' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Whether `comment` is an outer doc comment, `///` or `/** */`.
+fn is_doc_comment(comment: &str) -> bool {
+    (comment.starts_with("///") && !comment.starts_with("////"))
+        || (comment.starts_with("/**") && !comment.starts_with("/***") && comment != "/**/")
+}
+
+/// Whether the written attribute `attr` is `#[src_embed]`, directly or
+/// through `#[cfg_attr]`.
+fn is_src_embed(attr: &str) -> bool {
+    let Ok(AttributeText(attr)) = syn::parse_str(attr) else {
+        return false;
+    };
+    if attrs::is_src_embed(&attr) {
+        return true;
+    }
+    if !attr.path().is_ident("cfg_attr") {
+        return false;
+    }
+    attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+        .is_ok_and(|metas| {
+            metas.iter().skip(1).any(|meta| {
+                meta.path()
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "src_embed")
+            })
+        })
+}
+
+/// A single outer attribute, parsed from its text.
+struct AttributeText(Attribute);
+
+impl Parse for AttributeText {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut attrs = Attribute::parse_outer(input)?;
+        match attrs.len() {
+            1 => Ok(AttributeText(attrs.remove(0))),
+            _ => Err(input.error("expected one attribute")),
+        }
+    }
+}
+
+/// Whether two streams hold the same tokens. The spacing of punctuation is
+/// ignored, since a stream assembled from another one's tokens renders it
+/// differently.
+fn same_tokens(a: &TokenStream, b: &TokenStream) -> bool {
+    let (a, b): (Vec<TokenTree>, Vec<TokenTree>) = (
+        a.clone().into_iter().collect(),
+        b.clone().into_iter().collect(),
+    );
+    a.len() == b.len()
+        && a.iter().zip(&b).all(|pair| match pair {
+            (TokenTree::Group(a), TokenTree::Group(b)) => {
+                a.delimiter() == b.delimiter() && same_tokens(&a.stream(), &b.stream())
+            }
+            (TokenTree::Punct(a), TokenTree::Punct(b)) => a.as_char() == b.as_char(),
+            (TokenTree::Ident(a), TokenTree::Ident(b)) => a == b,
+            (TokenTree::Literal(a), TokenTree::Literal(b)) => a.to_string() == b.to_string(),
+            _ => false,
+        })
+}
+
+/// Shift the lines of a raw capture left so the item starts at column zero.
+///
+/// `column` is the column of the item's first token in its file, which the
+/// first line of `text` already starts after. Every following line loses as
+/// much leading whitespace, up to `column` characters, keeping the offsets
+/// between lines. Without a column, the whitespace common to all following
+/// non-blank lines is removed instead.
+///
+/// The text is returned unchanged if shifting it would alter the contents of
+/// a multi-line literal.
+pub fn reindent(text: &str, column: Option<usize>) -> String {
+    let mut lines = text.split('\n');
+    let first = lines.next().unwrap_or_default();
+    let rest: Vec<&str> = lines.collect();
+    let indent = |line: &str| line.chars().take_while(|c| c.is_whitespace()).count();
+    let shift = column.unwrap_or_else(|| {
+        rest.iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| indent(line))
+            .min()
+            .unwrap_or(0)
+    });
+
+    let mut shifted = first.to_string();
+    for line in rest {
+        let strip = indent(line).min(shift);
+        let start = line
+            .char_indices()
+            .nth(strip)
+            .map_or(line.len(), |(i, _)| i);
+        shifted.push('\n');
+        shifted.push_str(&line[start..]);
+    }
+
+    let tokens = |text: &str| {
+        text.parse::<TokenStream>()
+            .map(|tokens| tokens.to_string())
+            .ok()
+    };
+    if tokens(&shifted).is_some() && tokens(&shifted) == tokens(text) {
+        shifted
+    } else {
+        text.to_string()
+    }
+}
+
+/// Expand the tabs in the leading whitespace of each line of a raw capture
+/// to spaces, up to the next multiple of `width` columns.
+///
+/// Tabs after the first other character of a line are kept. Like
+/// [`reindent`], the text is returned unchanged if the expansion would
+/// alter the contents of a multi-line literal.
+pub fn expand_tabs(text: &str, width: usize) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            expanded.push('\n');
+        }
+        let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+        let mut column = 0;
+        for ch in line[..indent].chars() {
+            let spaces = if ch == '\t' {
+                width - column % width
+            } else {
+                1
+            };
+            expanded.extend(std::iter::repeat_n(' ', spaces));
+            column += spaces;
+        }
+        expanded.push_str(&line[indent..]);
+    }
+
+    let tokens = |text: &str| {
+        text.parse::<TokenStream>()
+            .map(|tokens| tokens.to_string())
+            .ok()
+    };
+    if tokens(&expanded).is_some() && tokens(&expanded) == tokens(text) {
+        expanded
+    } else {
+        text.to_string()
+    }
+}
+
+/// The column of the first token of `input` in its file, if known.
+pub fn first_column(input: &TokenStream) -> Option<usize> {
+    let start = input.clone().into_iter().next()?.span().start();
+    // Spans without location information report line 0.
+    (start.line > 0).then_some(start.column)
+}
+
+/// The contents of a source file, indexed by line.
+struct SourceText {
+    text: String,
+    line_starts: Vec<usize>,
+}
+
+/// A file's path, modification time and length, with its contents.
+type Read = (PathBuf, SystemTime, u64, Rc<SourceText>);
+
+thread_local! {
+    /// The file read last.
+    ///
+    /// Every item of a file reads the same text, so without this a file with
+    /// many embedded items would be read and indexed once per item.
+    static LAST_READ: RefCell<Option<Read>> = const { RefCell::new(None) };
+}
+
+impl SourceText {
+    /// The contents of `file`, reusing the previous read while the file is
+    /// unchanged.
+    fn read(file: &Path) -> Option<Rc<SourceText>> {
+        let metadata = fs::metadata(file).ok()?;
+        let (modified, len) = (metadata.modified().ok()?, metadata.len());
+        LAST_READ.with(|last| {
+            let mut last = last.borrow_mut();
+            if let Some((path, time, size, text)) = &*last {
+                if path == file && *time == modified && *size == len {
+                    return Some(Rc::clone(text));
+                }
+            }
+            let text = Rc::new(SourceText::new(fs::read_to_string(file).ok()?));
+            *last = Some((file.to_path_buf(), modified, len, Rc::clone(&text)));
+            Some(text)
+        })
+    }
+
+    fn new(text: String) -> Self {
+        let text = text
+            .strip_prefix('\u{feff}')
+            .unwrap_or(&text)
+            .replace("\r\n", "\n");
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        SourceText { text, line_starts }
+    }
+
+    /// The byte offset of a 1-based line and 0-based character column.
+    fn offset(&self, position: LineColumn) -> Option<usize> {
+        let line_start = *self.line_starts.get(position.line.checked_sub(1)?)?;
+        let line = &self.text[line_start..];
+        let line = &line[..line.find('\n').unwrap_or(line.len())];
+        match line.char_indices().nth(position.column) {
+            Some((column, _)) => Some(line_start + column),
+            None if line.chars().count() == position.column => Some(line_start + line.len()),
+            None => None,
+        }
+    }
+
+    fn slice(&self, start: LineColumn, end: LineColumn) -> Option<&str> {
+        self.text.get(self.offset(start)?..self.offset(end)?)
+    }
+}
+
+/// Remove attributes from the text between two tokens of the item.
+///
+/// The text otherwise only holds whitespace and comments. An attribute that
+/// sits on a line of its own is removed together with that line.
+fn strip_attributes(gap: &str) -> String {
+    let mut kept = String::with_capacity(gap.len());
+    let mut rest = gap;
+    while let Some(ch) = rest.chars().next() {
+        if rest.starts_with("//") {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            kept.push_str(&rest[..len]);
+            rest = &rest[len..];
+        } else if rest.starts_with("/*") {
+            let len = block_comment_len(rest);
+            kept.push_str(&rest[..len]);
+            rest = &rest[len..];
+        } else if ch == '#' {
+            let len = attribute_len(rest);
+            rest = &rest[len..];
+            let line_start = kept.rfind('\n').map_or(0, |i| i + 1);
+            if kept[line_start..].trim().is_empty() {
+                if let Some(newline) = rest.find('\n') {
+                    if rest[..newline].trim().is_empty() {
+                        kept.truncate(line_start);
+                        rest = &rest[newline + 1..];
+                    }
+                }
+            }
+        } else {
+            kept.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+    kept
+}
+
+/// The length of the (possibly nested) block comment at the start of `text`.
+fn block_comment_len(text: &str) -> usize {
+    let mut depth = 0;
+    let mut i = 0;
+    while i < text.len() {
+        if text[i..].starts_with("/*") {
+            depth += 1;
+            i += 2;
+        } else if text[i..].starts_with("*/") {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += text[i..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    text.len()
+}
+
+/// The length of the `#[...]` attribute at the start of `text`.
+fn attribute_len(text: &str) -> usize {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut chars = text.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' => in_string = !in_string,
+            '[' if !in_string => depth += 1,
+            ']' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    text.len()
+}
@@ -0,0 +1,167 @@
+//! `normalize_commas`: the captured text without trailing commas.
+
+use proc_macro2::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+
+use crate::lexer;
+
+/// `input` without its trailing comma tokens: those ending a group or
+/// followed by a `>`.
+///
+/// Renderings from tokens start from these, so that the spacing around the
+/// delimiter after a comma does not depend on whether the comma was there.
+/// The comma of a one-element tuple, the only one in its parentheses, is
+/// kept: without it `(u8,)` would become the parenthesized type `(u8)`.
+pub fn strip(input: &TokenStream) -> TokenStream {
+    strip_tokens(input, false)
+}
+
+/// [`strip`] for the tokens of a group, which are `parenthesized` if it is
+/// delimited by `(...)`.
+fn strip_tokens(input: &TokenStream, parenthesized: bool) -> TokenStream {
+    let tokens: Vec<TokenTree> = input.clone().into_iter().collect();
+    let tuple = parenthesized && top_level_commas(&tokens) <= 1;
+    let mut stripped = Vec::with_capacity(tokens.len());
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == ',' => {
+                let trailing = match tokens.get(i + 1) {
+                    None => !tuple,
+                    Some(TokenTree::Punct(next)) => next.as_char() == '>',
+                    Some(_) => false,
+                };
+                if !trailing {
+                    stripped.push(token.clone());
+                }
+            }
+            TokenTree::Group(group) => {
+                let parenthesized = group.delimiter() == Delimiter::Parenthesis;
+                let stream = strip_tokens(&group.stream(), parenthesized);
+                let mut inner = Group::new(group.delimiter(), stream);
+                inner.set_span(group.span());
+                stripped.push(TokenTree::Group(inner));
+            }
+            _ => stripped.push(token.clone()),
+        }
+    }
+    stripped.into_iter().collect()
+}
+
+/// The number of commas in `tokens` outside of the `<...>` of generics.
+///
+/// A `<` that is really a comparison only hides commas from the count, so a
+/// count of at most one is taken as a tuple, which at worst keeps a trailing
+/// comma that could have gone.
+fn top_level_commas(tokens: &[TokenTree]) -> usize {
+    let mut commas = 0;
+    let mut angles = 0usize;
+    for (i, token) in tokens.iter().enumerate() {
+        let TokenTree::Punct(punct) = token else {
+            continue;
+        };
+        match punct.as_char() {
+            ',' if angles == 0 => commas += 1,
+            '<' => angles += 1,
+            '>' => {
+                let arrow = matches!(
+                    i.checked_sub(1).map(|prev| &tokens[prev]),
+                    Some(TokenTree::Punct(prev))
+                        if matches!(prev.as_char(), '-' | '=') && prev.spacing() == Spacing::Joint
+                );
+                if !arrow {
+                    angles = angles.saturating_sub(1);
+                }
+            }
+            _ => {}
+        }
+    }
+    commas
+}
+
+/// `source` with every trailing comma removed.
+///
+/// A comma is trailing when the next thing after it, skipping whitespace and
+/// comments, closes a `)`, `]`, `}` or `>`, except for the comma of a
+/// one-element tuple, as for [`strip`]. Commas in string, character and
+/// byte literals and in comments are text, not punctuation, and stay. Nothing
+/// else changes, so the layout and comments of a `raw` capture are kept.
+pub fn normalize(source: &str) -> String {
+    let commas = trailing_commas(source.as_bytes());
+    if commas.is_empty() {
+        return source.to_string();
+    }
+    let mut normalized = String::with_capacity(source.len());
+    let mut start = 0;
+    for comma in commas {
+        normalized.push_str(&source[start..comma]);
+        start = comma + 1;
+    }
+    normalized.push_str(&source[start..]);
+    normalized
+}
+
+/// The byte offsets of the trailing commas in `text`, in order.
+fn trailing_commas(text: &[u8]) -> Vec<usize> {
+    /// The delimiters open around the scan, with the commas found directly
+    /// inside each and how many `<` of generics are open in it.
+    struct Open {
+        delimiter: u8,
+        commas: usize,
+        angles: usize,
+    }
+
+    let mut open = vec![Open {
+        delimiter: 0,
+        commas: 0,
+        angles: 0,
+    }];
+    let mut commas = Vec::new();
+    let mut pending = None;
+    let mut prev = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let byte = text[i];
+        if byte.is_ascii_whitespace() {
+            i += 1;
+            continue;
+        }
+        if let Some(end) = lexer::comment_end(text, i) {
+            i = end;
+            continue;
+        }
+        let top = open.len() - 1;
+        match byte {
+            b')' | b']' | b'}' => {
+                let tuple = byte == b')' && open[top].delimiter == b'(' && open[top].commas <= 1;
+                if !tuple {
+                    commas.extend(pending);
+                }
+                if top > 0 {
+                    open.pop();
+                }
+            }
+            b'>' => {
+                commas.extend(pending);
+                if !matches!(prev, b'-' | b'=') {
+                    open[top].angles = open[top].angles.saturating_sub(1);
+                }
+            }
+            b'(' | b'[' | b'{' => open.push(Open {
+                delimiter: byte,
+                commas: 0,
+                angles: 0,
+            }),
+            b'<' => open[top].angles += 1,
+            b',' if open[top].angles == 0 => open[top].commas += 1,
+            _ => {}
+        }
+        pending = None;
+        prev = byte;
+        if byte == b',' {
+            pending = Some(i);
+            i += 1;
+        } else {
+            i = lexer::literal_end(text, i).unwrap_or(i + 1);
+        }
+    }
+    commas
+}
@@ -0,0 +1,93 @@
+//! `group_by_kind`: one module of constants per item kind.
+//!
+//! Separate attribute invocations cannot add items to a shared module, so the
+//! grouping is done by a single invocation on the enclosing module. It
+//! expands the `#[src_embed]` items of that module itself and collects the
+//! constants of each kind into a submodule.
+
+use proc_macro2::{Delimiter, Group, Span, TokenStream, TokenTree};
+use quote::quote;
+use syn::parse::{ParseStream, Parser};
+use syn::Item;
+
+use crate::items::{self, SourceItem};
+
+/// The submodules, in the order they are emitted.
+const KINDS: &[&str] = &[
+    "struct_sources",
+    "enum_sources",
+    "fn_sources",
+    "trait_sources",
+    "impl_sources",
+    "item_sources",
+];
+
+/// Expand `#[src_embed(group_by_kind)]` applied to the module `input`.
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let module: syn::ItemMod = syn::parse2(input.clone()).map_err(|err| {
+        syn::Error::new(err.span(), "`group_by_kind` can only be applied to a `mod`")
+    })?;
+    if module.content.is_none() {
+        return Err(syn::Error::new_spanned(
+            &module,
+            "`group_by_kind` needs a module with a body, not `mod name;`",
+        ));
+    }
+
+    // A module with a body ends in its braces; everything before them is
+    // kept as written.
+    let mut header: Vec<TokenTree> = input.into_iter().collect();
+    let Some(TokenTree::Group(braces)) = header.pop() else {
+        unreachable!("a module with a body ends in braces");
+    };
+    let (inner_attrs, items) = (|input: ParseStream| {
+        let inner_attrs = input.call(syn::Attribute::parse_inner)?;
+        Ok((inner_attrs, items::parse_items(input)?))
+    })
+    .parse2(braces.stream())?;
+
+    let mut contents = quote!(#(#inner_attrs)*);
+    let mut groups: Vec<Vec<TokenStream>> = vec![Vec::new(); KINDS.len()];
+    for SourceItem { options, tokens } in items {
+        match options {
+            Some(options) if options.group_by_kind => contents.extend(expand(tokens)?),
+            Some(options) => {
+                let (consts, item) = crate::embed(&options, tokens, None)?;
+                let group = KINDS.iter().position(|name| *name == group_name(&item));
+                groups[group.expect("every group name is listed in KINDS")].push(consts);
+                contents.extend(quote!(#item));
+            }
+            None => contents.extend(tokens),
+        }
+    }
+
+    for (name, consts) in KINDS.iter().zip(groups) {
+        if consts.is_empty() {
+            continue;
+        }
+        let name = syn::Ident::new(name, Span::call_site());
+        contents.extend(quote! {
+            #[doc(hidden)]
+            pub mod #name {
+                #(#consts)*
+            }
+        });
+    }
+
+    let mut body = Group::new(Delimiter::Brace, contents);
+    body.set_span(braces.span());
+    header.push(TokenTree::Group(body));
+    Ok(header.into_iter().collect())
+}
+
+/// The name of the submodule collecting the constants of `item`.
+fn group_name(item: &Item) -> &'static str {
+    match item {
+        Item::Struct(_) => "struct_sources",
+        Item::Enum(_) => "enum_sources",
+        Item::Fn(_) => "fn_sources",
+        Item::Trait(_) => "trait_sources",
+        Item::Impl(_) => "impl_sources",
+        _ => "item_sources",
+    }
+}
@@ -0,0 +1,289 @@
+//! Semantic categories of the identifiers in an item, for `highlight`.
+//!
+//! A lexer can tell identifiers from keywords but not a type from a value.
+//! This walks the parsed item instead and files every identifier under the
+//! role the syntax gives it.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::visit::{self, Visit};
+use syn::{Expr, Item};
+
+use crate::naming;
+
+/// The categories, in the order they are written to the JSON object.
+const CATEGORIES: &[&str] = &[
+    "types",
+    "traits",
+    "functions",
+    "fields",
+    "variants",
+    "values",
+    "lifetimes",
+    "macros",
+];
+
+/// Generate the `__FOO_HIGHLIGHT__` constant for `item`.
+///
+/// `attrs` are the attributes shared by every generated constant.
+pub fn expand(item: &Item, item_name: &str, attrs: &TokenStream) -> TokenStream {
+    let ident = syn::Ident::new(
+        &naming::const_name(item_name, "HIGHLIGHT"),
+        Span::call_site(),
+    );
+    let json = to_json(&categorize(item));
+    quote! {
+        #attrs
+        pub const #ident: &str = #json;
+    }
+}
+
+/// The identifiers of `item` per category, each listed once in order of
+/// first appearance.
+fn categorize(item: &Item) -> Vec<Vec<String>> {
+    let mut visitor = Categorizer {
+        found: vec![Vec::new(); CATEGORIES.len()],
+    };
+    visitor.visit_item(item);
+    visitor.found
+}
+
+/// `{"types":["Foo"],"traits":[],...}` with every category present.
+fn to_json(found: &[Vec<String>]) -> String {
+    let fields: Vec<String> = CATEGORIES
+        .iter()
+        .zip(found)
+        .map(|(category, names)| {
+            let names: Vec<String> = names.iter().map(|name| format!("\"{}\"", name)).collect();
+            format!("\"{}\":[{}]", category, names.join(","))
+        })
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+struct Categorizer {
+    found: Vec<Vec<String>>,
+}
+
+impl Categorizer {
+    fn add(&mut self, category: &str, name: impl ToString) {
+        let index = CATEGORIES
+            .iter()
+            .position(|c| *c == category)
+            .expect("every category is listed in CATEGORIES");
+        let name = name.to_string();
+        if !self.found[index].contains(&name) {
+            self.found[index].push(name);
+        }
+    }
+
+    /// File a name used as a value in an expression or pattern.
+    ///
+    /// Only the syntax is known, so the usual naming conventions decide:
+    /// `Bar` names a variant or a tuple or unit struct, while `bar` and `BAR`
+    /// name a variable or constant.
+    fn add_value(&mut self, ident: &syn::Ident) {
+        let name = ident.to_string();
+        if name.starts_with(char::is_uppercase) && name.chars().any(char::is_lowercase) {
+            self.add("variants", name);
+        } else {
+            self.add("values", name);
+        }
+    }
+
+    /// File the path of a braced struct expression or pattern: `Shape::Rect`
+    /// names a variant of the type `Shape`, `shapes::Rect` a struct.
+    fn add_struct_path(&mut self, path: &syn::Path) {
+        let mut segments = path.segments.iter().rev();
+        let Some(last) = segments.next() else {
+            return;
+        };
+        let in_type = segments
+            .next()
+            .is_some_and(|parent| parent.ident.to_string().starts_with(char::is_uppercase));
+        self.add(if in_type { "variants" } else { "types" }, &last.ident);
+    }
+
+    fn add_value_path(&mut self, path: &syn::Path) {
+        if let Some(last) = path.segments.last() {
+            self.add_value(&last.ident);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Categorizer {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        self.add("types", &node.ident);
+        visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast syn::ItemEnum) {
+        self.add("types", &node.ident);
+        visit::visit_item_enum(self, node);
+    }
+
+    fn visit_item_union(&mut self, node: &'ast syn::ItemUnion) {
+        self.add("types", &node.ident);
+        visit::visit_item_union(self, node);
+    }
+
+    fn visit_item_type(&mut self, node: &'ast syn::ItemType) {
+        self.add("types", &node.ident);
+        visit::visit_item_type(self, node);
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast syn::ItemTrait) {
+        self.add("traits", &node.ident);
+        visit::visit_item_trait(self, node);
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        if let Some((_, path, _)) = &node.trait_ {
+            if let Some(last) = path.segments.last() {
+                self.add("traits", &last.ident);
+            }
+        }
+        visit::visit_item_impl(self, node);
+    }
+
+    fn visit_item_const(&mut self, node: &'ast syn::ItemConst) {
+        self.add("values", &node.ident);
+        visit::visit_item_const(self, node);
+    }
+
+    fn visit_item_static(&mut self, node: &'ast syn::ItemStatic) {
+        self.add("values", &node.ident);
+        visit::visit_item_static(self, node);
+    }
+
+    fn visit_signature(&mut self, node: &'ast syn::Signature) {
+        self.add("functions", &node.ident);
+        visit::visit_signature(self, node);
+    }
+
+    fn visit_trait_item_type(&mut self, node: &'ast syn::TraitItemType) {
+        self.add("types", &node.ident);
+        visit::visit_trait_item_type(self, node);
+    }
+
+    fn visit_impl_item_type(&mut self, node: &'ast syn::ImplItemType) {
+        self.add("types", &node.ident);
+        visit::visit_impl_item_type(self, node);
+    }
+
+    fn visit_trait_item_const(&mut self, node: &'ast syn::TraitItemConst) {
+        self.add("values", &node.ident);
+        visit::visit_trait_item_const(self, node);
+    }
+
+    fn visit_impl_item_const(&mut self, node: &'ast syn::ImplItemConst) {
+        self.add("values", &node.ident);
+        visit::visit_impl_item_const(self, node);
+    }
+
+    fn visit_variant(&mut self, node: &'ast syn::Variant) {
+        self.add("variants", &node.ident);
+        visit::visit_variant(self, node);
+    }
+
+    fn visit_field(&mut self, node: &'ast syn::Field) {
+        if let Some(ident) = &node.ident {
+            self.add("fields", ident);
+        }
+        visit::visit_field(self, node);
+    }
+
+    fn visit_member(&mut self, node: &'ast syn::Member) {
+        if let syn::Member::Named(ident) = node {
+            self.add("fields", ident);
+        }
+    }
+
+    fn visit_type_param(&mut self, node: &'ast syn::TypeParam) {
+        self.add("types", &node.ident);
+        visit::visit_type_param(self, node);
+    }
+
+    fn visit_const_param(&mut self, node: &'ast syn::ConstParam) {
+        self.add("values", &node.ident);
+        visit::visit_const_param(self, node);
+    }
+
+    fn visit_type_path(&mut self, node: &'ast syn::TypePath) {
+        if let Some(last) = node.path.segments.last() {
+            self.add("types", &last.ident);
+        }
+        visit::visit_type_path(self, node);
+    }
+
+    fn visit_trait_bound(&mut self, node: &'ast syn::TraitBound) {
+        if let Some(last) = node.path.segments.last() {
+            self.add("traits", &last.ident);
+        }
+        visit::visit_trait_bound(self, node);
+    }
+
+    fn visit_lifetime(&mut self, node: &'ast syn::Lifetime) {
+        self.add("lifetimes", node);
+    }
+
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        if let Some(last) = node.path.segments.last() {
+            self.add("macros", &last.ident);
+        }
+        visit::visit_macro(self, node);
+    }
+
+    fn visit_pat_ident(&mut self, node: &'ast syn::PatIdent) {
+        // A lone `None` in a pattern parses as a binding.
+        self.add_value(&node.ident);
+        visit::visit_pat_ident(self, node);
+    }
+
+    fn visit_pat_tuple_struct(&mut self, node: &'ast syn::PatTupleStruct) {
+        self.add_value_path(&node.path);
+        visit::visit_pat_tuple_struct(self, node);
+    }
+
+    fn visit_pat_struct(&mut self, node: &'ast syn::PatStruct) {
+        self.add_struct_path(&node.path);
+        visit::visit_pat_struct(self, node);
+    }
+
+    fn visit_expr_struct(&mut self, node: &'ast syn::ExprStruct) {
+        self.add_struct_path(&node.path);
+        visit::visit_expr_struct(self, node);
+    }
+
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        self.add_value_path(&node.path);
+        visit::visit_expr_path(self, node);
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        let Expr::Path(callee) = &*node.func else {
+            return visit::visit_expr_call(self, node);
+        };
+        match callee.path.segments.last() {
+            // `Some(..)`, `Point(..)`: a tuple variant or struct.
+            Some(last) if last.ident.to_string().starts_with(char::is_uppercase) => {
+                self.add_value(&last.ident)
+            }
+            Some(last) => self.add("functions", &last.ident),
+            None => {}
+        }
+        // Visit the rest of the callee without filing its name again.
+        if let Some(qself) = &callee.qself {
+            self.visit_qself(qself);
+        }
+        visit::visit_path(self, &callee.path);
+        for arg in &node.args {
+            self.visit_expr(arg);
+        }
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        self.add("functions", &node.method);
+        visit::visit_expr_method_call(self, node);
+    }
+}
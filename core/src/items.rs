@@ -0,0 +1,78 @@
+//! Parsing a sequence of items while keeping the tokens they came from.
+//!
+//! Re-rendering a parsed `syn::Item` changes the spacing of the captured
+//! source, so macros that embed several items capture each one from its
+//! original tokens, exactly as `#[src_embed]` on the item would.
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use syn::parse::{Parse, ParseStream};
+
+use crate::{attrs, Options};
+
+/// An item of a block or module, as written.
+pub struct SourceItem {
+    /// The arguments of the item's `#[src_embed(...)]` attribute, if any.
+    pub options: Option<Options>,
+    /// The item's tokens without the `#[src_embed]` attribute.
+    pub tokens: TokenStream,
+}
+
+/// Parse items until the end of `input`.
+pub fn parse_items(input: ParseStream) -> syn::Result<Vec<SourceItem>> {
+    let mut items = Vec::new();
+    while !input.is_empty() {
+        let (mut item, tokens) = parse_with_tokens::<syn::Item>(input)?;
+        let options = match attrs::take_src_embed(&mut item) {
+            Some(attr) => Some(Options::from_attribute(&attr)?),
+            None => None,
+        };
+        items.push(SourceItem {
+            options,
+            tokens: without_src_embed(tokens),
+        });
+    }
+    Ok(items)
+}
+
+/// Parse a `T` from `input` together with the tokens it was parsed from.
+pub fn parse_with_tokens<T: Parse>(input: ParseStream) -> syn::Result<(T, Vec<TokenTree>)> {
+    let begin = input.cursor();
+    let parsed: T = input.parse()?;
+    let end = input.cursor();
+
+    let mut tokens = Vec::new();
+    let mut cursor = begin;
+    while cursor != end {
+        let Some((tt, next)) = cursor.token_tree() else {
+            break;
+        };
+        tokens.push(tt);
+        cursor = next;
+    }
+    Ok((parsed, tokens))
+}
+
+/// Remove the first `#[src_embed]` attribute from the outer attributes at
+/// the start of `tokens`.
+fn without_src_embed(mut tokens: Vec<TokenTree>) -> TokenStream {
+    let mut i = 0;
+    while let (Some(TokenTree::Punct(hash)), Some(TokenTree::Group(group))) =
+        (tokens.get(i), tokens.get(i + 1))
+    {
+        if hash.as_char() != '#' || group.delimiter() != Delimiter::Bracket {
+            break;
+        }
+        let is_src_embed = syn::parse2::<syn::Meta>(group.stream()).is_ok_and(|meta| {
+            meta.path()
+                .segments
+                .last()
+                .is_some_and(|s| s.ident == "src_embed")
+        });
+        if is_src_embed {
+            tokens.drain(i..i + 2);
+            break;
+        }
+        i += 2;
+    }
+    tokens.into_iter().collect()
+}
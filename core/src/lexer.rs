@@ -0,0 +1,146 @@
+//! Finding the comments, literals and identifiers of Rust source text.
+//!
+//! Passes that rewrite the embedded text work on it directly, so that the
+//! layout of every capture mode is kept, and use these to tell code from
+//! what only looks like code. Every byte the scan acts on is ASCII, and the
+//! bytes of a multi-byte character never are, so the text can be walked
+//! byte by byte.
+
+/// The offset after the comment starting at `at`, if one does.
+pub fn comment_end(text: &[u8], at: usize) -> Option<usize> {
+    match (text.get(at)?, text.get(at + 1)) {
+        (b'/', Some(b'/')) => Some(
+            text[at..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(text.len(), |end| at + end),
+        ),
+        (b'/', Some(b'*')) => Some(block_comment_end(text, at)),
+        _ => None,
+    }
+}
+
+/// The offset after the string or character literal starting at `at`, if one
+/// does: plain, byte, C and raw strings and characters and bytes. A quote
+/// that begins a lifetime or label is not a literal.
+pub fn literal_end(text: &[u8], at: usize) -> Option<usize> {
+    if at > 0 && is_ident_byte(text[at - 1]) {
+        return None;
+    }
+    let quote = if matches!(text.get(at), Some(b'b' | b'c')) {
+        at + 1
+    } else {
+        at
+    };
+    match text.get(quote)? {
+        b'"' => Some(string_end(text, quote + 1)),
+        b'\'' => char_end(text, quote),
+        b'r' => raw_string_end(text, quote),
+        _ => None,
+    }
+}
+
+/// Whether `byte` can start an identifier.
+pub fn is_ident_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte >= 0x80
+}
+
+/// Whether `byte` can continue an identifier.
+pub fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte >= 0x80
+}
+
+/// The offset after the identifier starting at `at`.
+pub fn ident_end(text: &[u8], at: usize) -> usize {
+    text[at..]
+        .iter()
+        .position(|&b| !is_ident_byte(b))
+        .map_or(text.len(), |end| at + end)
+}
+
+/// The offset after the block comment starting at `start`, which may nest.
+fn block_comment_end(text: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < text.len() {
+        match (text[i], text.get(i + 1)) {
+            (b'/', Some(b'*')) => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', Some(b'/')) => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    text.len()
+}
+
+/// The offset after the closing quote of a string whose contents start at
+/// `start`.
+fn string_end(text: &[u8], start: usize) -> usize {
+    let mut i = start;
+    while i < text.len() {
+        match text[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    text.len()
+}
+
+/// The offset after the character literal starting with the quote at
+/// `start`, or `None` if the quote begins a lifetime or label.
+fn char_end(text: &[u8], start: usize) -> Option<usize> {
+    match text.get(start + 1)? {
+        b'\\' => {
+            // Skip the escaped character, which may itself be a quote.
+            let rest = start + 3;
+            Some(
+                text.get(rest..)
+                    .and_then(|rest| rest.iter().position(|&b| b == b'\''))
+                    .map_or(text.len(), |end| rest + end + 1),
+            )
+        }
+        &first => {
+            let len = match first {
+                0x00..=0x7f => 1,
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                _ => 4,
+            };
+            (text.get(start + 1 + len) == Some(&b'\'')).then_some(start + len + 2)
+        }
+    }
+}
+
+/// The offset after the raw string literal whose `r` is at `start`, if one
+/// starts there rather than a raw identifier.
+fn raw_string_end(text: &[u8], start: usize) -> Option<usize> {
+    let hashes = text[start + 1..].iter().take_while(|&&b| b == b'#').count();
+    let open = start + 1 + hashes;
+    if text.get(open) != Some(&b'"') {
+        return None;
+    }
+    let mut i = open + 1;
+    while i < text.len() {
+        if text[i] == b'"'
+            && text[i + 1..]
+                .iter()
+                .take(hashes)
+                .filter(|&&b| b == b'#')
+                .count()
+                == hashes
+        {
+            return Some(i + 1 + hashes);
+        }
+        i += 1;
+    }
+    Some(text.len())
+}
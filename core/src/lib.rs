@@ -0,0 +1,432 @@
+//! Implementation of the `src_embed` macros.
+//!
+//! This crate holds the argument parsing, name derivation and code generation
+//! behind `#[src_embed]`. It works on `proc_macro2` token streams so the same
+//! logic can be shared by the procedural macro crate and by the runtime
+//! helpers of the `src_embed` crate. It is an implementation detail and has
+//! no stable API; depend on `src_embed` instead.
+
+mod all;
+mod anonymize;
+mod assert;
+pub mod attrs;
+mod build_info;
+mod capture;
+mod commas;
+mod group;
+#[cfg(feature = "highlight")]
+mod highlight;
+mod items;
+mod lexer;
+#[cfg(feature = "manifest")]
+mod manifest;
+mod metadata;
+pub mod naming;
+mod nested;
+mod options;
+mod pretty;
+mod public;
+mod sha256;
+#[cfg(feature = "token-spans")]
+mod spans;
+mod strip_macros;
+mod summary;
+mod sync_check;
+mod trait_impls;
+
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::{quote, ToTokens};
+
+pub use options::{Case, Header, Options};
+
+/// Expand `#[src_embed(args)]` applied to `input`.
+///
+/// Errors are reported as `compile_error!` invocations in the returned
+/// stream.
+pub fn expand(args: TokenStream, input: TokenStream) -> TokenStream {
+    let expanded = Options::from_args(args).and_then(|options| {
+        if options.group_by_kind {
+            group::expand(input)
+        } else {
+            let (consts, item) = embed(&options, input, None)?;
+            Ok(quote! {
+                #consts
+
+                #item
+            })
+        }
+    });
+    expanded.unwrap_or_else(|err| err.to_compile_error())
+}
+
+/// Expand `src_embed_all! { ... }`.
+///
+/// Errors are reported as `compile_error!` invocations in the returned
+/// stream.
+pub fn expand_all(input: TokenStream) -> TokenStream {
+    all::expand(input).unwrap_or_else(|err| err.to_compile_error())
+}
+
+/// Expand `src_embed_trait_impls! { ... }`.
+///
+/// Errors are reported as `compile_error!` invocations in the returned
+/// stream.
+pub fn expand_trait_impls(input: TokenStream) -> TokenStream {
+    trait_impls::expand(input).unwrap_or_else(|err| err.to_compile_error())
+}
+
+/// Expand `assert_source_eq!(Item, "fixture")`.
+///
+/// Errors are reported as `compile_error!` invocations in the returned
+/// stream.
+#[cfg(feature = "test-utils")]
+pub fn expand_assert_source_eq(input: TokenStream) -> TokenStream {
+    assert::expand(input).unwrap_or_else(|err| err.to_compile_error())
+}
+
+/// Expand `assert_source_len_eq!(A, B)`.
+///
+/// Errors are reported as `compile_error!` invocations in the returned
+/// stream.
+pub fn expand_assert_source_len_eq(input: TokenStream) -> TokenStream {
+    assert::expand_len(input).unwrap_or_else(|err| err.to_compile_error())
+}
+
+/// Generate the constants embedding the item `input`.
+///
+/// Returns them together with the parsed item, which is re-emitted
+/// unchanged. With `dedup`, a source constant whose value was already
+/// emitted refers to the earlier constant instead of repeating it.
+fn embed(
+    options: &Options,
+    input: TokenStream,
+    mut dedup: Option<&mut all::Dedup>,
+) -> syn::Result<(TokenStream, syn::Item)> {
+    let sources = if options.public_only || options.header_only {
+        let stripped = rendered_input(options, &input);
+        let rendered = stripped.as_ref().unwrap_or(&input);
+        if options.public_only {
+            public::capture_sources(options, rendered)?
+        } else {
+            vec![("SOURCE", summary::header(rendered)?)]
+        }
+    } else {
+        capture_sources(options, &input)
+    };
+
+    let nested_input = (options.nested && !options.nested_item).then(|| input.clone());
+
+    // Parse the input - accepts any Rust item (trait, impl, struct, etc.)
+    let input_parsed = syn::parse2::<syn::Item>(input)?;
+
+    // Extract the name of the item to generate a unique const name
+    let item_name = naming::item_name(&input_parsed, options);
+    if syn::parse_str::<syn::Ident>(&naming::source_const_name(&item_name)).is_err() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!("`{}` does not form a valid constant name", item_name),
+        ));
+    }
+
+    let cfg_attr = options
+        .cfg_predicate()
+        .map(|predicate| quote!(#[cfg(#predicate)]));
+    // Only the default cases follow the naming convention for constants.
+    let allow_case = matches!(options.case, Case::Lower | Case::Preserve)
+        .then(|| quote!(#[allow(non_upper_case_globals)]));
+    // Embedded sources are often left unused on purpose.
+    let allow_dead_code = (!options.dead_code_lint).then(|| quote!(#[allow(dead_code)]));
+    let doc = if options.visible_docs {
+        let doc = format!(
+            "Generated by `#[src_embed]` for `{}`.",
+            naming::display_name(&input_parsed, options)
+        );
+        quote!(#[doc = #doc])
+    } else {
+        quote!(#[doc(hidden)])
+    };
+    let const_attrs = quote! {
+        #cfg_attr
+        #allow_case
+        #allow_dead_code
+        #doc
+    };
+    let metadata = metadata::expand(&input_parsed, &item_name, &const_attrs);
+    let keyword = if options.as_static {
+        quote!(static)
+    } else {
+        quote!(const)
+    };
+    let mut source_consts = Vec::new();
+    #[cfg(feature = "token-spans")]
+    let mut token_spans = None;
+    let renames = options.anonymize.then(|| anonymize::renames(&input_parsed));
+    for (suffix, source) in sources {
+        let const_ident =
+            syn::Ident::new(&naming::const_name(&item_name, suffix), Span::call_site());
+        let mut source = if options.normalize_commas {
+            commas::normalize(&source)
+        } else {
+            source
+        };
+        if options.strip_macros {
+            source = strip_macros::placeholders(&source);
+        }
+        if let Some(renames) = &renames {
+            source = anonymize::rename(&source, renames);
+        }
+        if options.golden && !source.ends_with('\n') {
+            source.push('\n');
+        }
+        let source = add_header(options, source);
+        if let (Some(path), "SOURCE", false) = (&options.sync_check, suffix, options.nested_item) {
+            let display = naming::display_name(&input_parsed, options);
+            source_consts.push(sync_check::expand(path, &source, &display)?);
+        }
+        if options.cstr && suffix == "SOURCE" {
+            source_consts.push(cstr_const(&source, &item_name, &const_attrs)?);
+        }
+        #[cfg(feature = "token-spans")]
+        if suffix == "SOURCE" {
+            token_spans = Some(spans::expand(&source, &item_name, &const_attrs));
+        }
+        let value = match &mut dedup {
+            Some(dedup) => dedup.value(&const_ident, &const_attrs, &keyword, source),
+            None => source_literal(&source).into_token_stream(),
+        };
+        #[cfg(feature = "manifest")]
+        let value = if suffix == "SOURCE" {
+            let name = naming::display_name(&input_parsed, options);
+            manifest::register(&name, naming::kind(&input_parsed), value)
+        } else {
+            value
+        };
+        source_consts.push(quote! {
+            #const_attrs
+            pub #keyword #const_ident: &str = #value;
+        });
+    }
+    let opts_ident = syn::Ident::new(
+        &naming::const_name(&item_name, "EMBED_OPTS"),
+        Span::call_site(),
+    );
+    let opts = options.to_args();
+    let lang_ident = syn::Ident::new(
+        &naming::const_name(&item_name, "SOURCE_LANG"),
+        Span::call_site(),
+    );
+    let lang = options.lang();
+    let opts_const = quote! {
+        #const_attrs
+        pub const #opts_ident: &str = #opts;
+        #const_attrs
+        pub const #lang_ident: &str = #lang;
+    };
+    let cow_accessor = options.cow.then(|| {
+        let fn_ident = syn::Ident::new(
+            &naming::fn_name(&item_name, "source_cow"),
+            Span::call_site(),
+        );
+        let const_ident =
+            syn::Ident::new(&naming::source_const_name(&item_name), Span::call_site());
+        // Every capture mode stores a `&'static str` today, so the accessor
+        // borrows; modes that build the text at runtime will return `Owned`.
+        quote! {
+            #const_attrs
+            pub fn #fn_ident() -> ::std::borrow::Cow<'static, str> {
+                ::std::borrow::Cow::Borrowed(#const_ident)
+            }
+        }
+    });
+    let nested = match &nested_input {
+        Some(tokens) => Some(nested::expand(options, tokens, &input_parsed)?),
+        None => None,
+    };
+    let build_info = (options.build_info || options.edition.is_some())
+        .then(|| build_info::expand(options, &item_name, &const_attrs));
+    #[cfg(feature = "highlight")]
+    let highlight = options
+        .highlight
+        .then(|| highlight::expand(&input_parsed, &item_name, &const_attrs));
+    #[cfg(not(feature = "highlight"))]
+    let highlight: Option<TokenStream> = None;
+    #[cfg(not(feature = "token-spans"))]
+    let token_spans: Option<TokenStream> = None;
+    let consts = quote! {
+        #(#source_consts)*
+        #opts_const
+        #cow_accessor
+        #metadata
+        #build_info
+        #highlight
+        #token_spans
+        #nested
+    };
+    Ok((consts, input_parsed))
+}
+
+/// Capture the text of `input` in the modes requested by the options.
+///
+/// Returns the suffix of each source constant with the text it holds. The
+/// first one is always `SOURCE`; with both `raw` and `pretty` the formatted
+/// text goes to a second `SOURCE_PRETTY` constant. A mode that cannot apply
+/// to the input falls back to the text rendered from the tokens.
+fn capture_sources(options: &Options, input: &TokenStream) -> Vec<(&'static str, String)> {
+    let written = capture::written(input);
+    // With `normalize_commas` the token renderings start from tokens without
+    // trailing commas, and the raw text is normalized afterwards.
+    let stripped = rendered_input(options, input);
+    let stripped_written = stripped.as_ref().map(capture::written);
+    let (rendered, rendered_written) = match (&stripped, &stripped_written) {
+        (Some(stripped), Some(written)) => (stripped, written),
+        _ => (input, &written),
+    };
+    let reflow = || match rendered_written {
+        Some(written) => written.reflow(),
+        None => capture::reflow(rendered),
+    };
+    let raw = || {
+        let (text, column) = match &written {
+            Some(written) => (written.original(), Some(written.column())),
+            None => (capture::original(input), capture::first_column(input)),
+        };
+        let text = match text {
+            Some(text) if options.reindent => capture::reindent(&text, column),
+            Some(text) => text,
+            None => return reflow(),
+        };
+        match options.tabs {
+            Some(width) => capture::expand_tabs(&text, width),
+            None => text,
+        }
+    };
+    let pretty = || {
+        let tokens = rendered_written
+            .as_ref()
+            .map_or_else(|| rendered.clone(), capture::Written::tokens);
+        pretty::pretty(&tokens).unwrap_or_else(reflow)
+    };
+    match (options.raw, options.formatted()) {
+        (true, true) => vec![("SOURCE", raw()), ("SOURCE_PRETTY", pretty())],
+        (true, false) => vec![("SOURCE", raw())],
+        (false, true) => vec![("SOURCE", pretty())],
+        (false, false) => vec![("SOURCE", reflow())],
+    }
+}
+
+/// The tokens the source is rendered from when arguments change them before
+/// rendering: without trailing commas for `normalize_commas`, with macro
+/// bodies replaced for `strip_macros`. `None` when no argument does.
+fn rendered_input(options: &Options, input: &TokenStream) -> Option<TokenStream> {
+    let mut rendered = options.strip_macros.then(|| strip_macros::strip(input));
+    if options.normalize_commas {
+        rendered = Some(commas::strip(rendered.as_ref().unwrap_or(input)));
+    }
+    rendered
+}
+
+/// Generate `__FOO_SOURCE_CSTR__`, the source as a NUL-terminated C string.
+///
+/// The constant is built with `CStr::from_bytes_with_nul` rather than a
+/// `c"..."` literal, which is not available in every edition.
+fn cstr_const(
+    source: &str,
+    item_name: &str,
+    const_attrs: &TokenStream,
+) -> syn::Result<TokenStream> {
+    if source.contains('\0') {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`cstr` needs a source without NUL characters, which would end the C string early",
+        ));
+    }
+    let ident = syn::Ident::new(
+        &naming::const_name(item_name, "SOURCE_CSTR"),
+        Span::call_site(),
+    );
+    let bytes = Literal::byte_string(format!("{}\0", source).as_bytes());
+    Ok(quote! {
+        #const_attrs
+        pub const #ident: &::core::ffi::CStr =
+            match ::core::ffi::CStr::from_bytes_with_nul(#bytes) {
+                ::core::result::Result::Ok(source) => source,
+                ::core::result::Result::Err(_) => {
+                    panic!("the embedded source holds no NUL characters")
+                }
+            };
+    })
+}
+
+/// Prepend the header lines requested through `cfg_note = "..."` and
+/// `header = "..."`, if any.
+///
+/// The note is part of the source the checksum is taken of.
+fn add_header(options: &Options, source: String) -> String {
+    let source = match &options.cfg_note {
+        Some(note) => format!("// cfg: {}\n{}", note, source),
+        None => source,
+    };
+    match options.header {
+        Some(Header::Checksum) => {
+            format!(
+                "// sha: {}\n{}",
+                sha256::hex_digest(source.as_bytes()),
+                source
+            )
+        }
+        None => source,
+    }
+}
+
+/// Build the string literal that carries the embedded source.
+///
+/// Sources with quotes or backslashes are written as a raw string literal,
+/// `r#"..."#` with as few `#` as their contents allow, which avoids the
+/// escape sequences. Otherwise, and whenever the source holds a control
+/// character other than a newline or a tab, the literal is escaped here
+/// rather than through `LitStr::new`, so the emitted tokens do not depend on
+/// how the compiler's proc-macro server chooses to render control
+/// characters. Every character that cannot appear verbatim in a `"..."`
+/// literal is then written as an escape sequence. Either way the runtime
+/// value of the constant equals `source` exactly.
+fn source_literal(source: &str) -> Literal {
+    if let Some(literal) = raw_literal(source) {
+        return literal;
+    }
+    let mut escaped = String::with_capacity(source.len() + 2);
+    escaped.push('"');
+    for ch in source.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\0' => escaped.push_str("\\0"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+        .parse()
+        .expect("escaped source is always a valid string literal")
+}
+
+/// The raw string literal holding `source`, when it saves escapes and can
+/// hold the text verbatim.
+fn raw_literal(source: &str) -> Option<Literal> {
+    let verbatim = |ch: char| !ch.is_control() || ch == '\n' || ch == '\t';
+    if !source.contains(['"', '\\']) || !source.chars().all(verbatim) {
+        return None;
+    }
+    // One `#` more than the longest run following a quote in the source.
+    let hashes = source
+        .split('"')
+        .skip(1)
+        .map(|rest| rest.len() - rest.trim_start_matches('#').len())
+        .max()
+        .map_or(0, |run| run + 1);
+    let hashes = "#".repeat(hashes);
+    format!("r{hashes}\"{source}\"{hashes}").parse().ok()
+}
@@ -0,0 +1,52 @@
+//! Registration of embedded sources in the `src_embed::manifest()`
+//! collection.
+//!
+//! A constant cannot be extended from several macro invocations, so each
+//! embedded item instead registers itself when the program starts. The
+//! registration is a function placed in the platform's list of constructors,
+//! the section that the C runtime walks before `main`, and pushes a static
+//! entry onto the list kept by the `src_embed` crate.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Wrap the value of the source constant so that evaluating the program
+/// registers `name`, `kind` and the source in the manifest.
+///
+/// The registration items are nested in the constant's initializer, which
+/// keeps them valid wherever the constant can be emitted, including as an
+/// associated constant of an `impl` block.
+pub fn register(name: &str, kind: &str, value: TokenStream) -> TokenStream {
+    quote! {
+        {
+            const SOURCE: &str = #value;
+            static ENTRY: ::src_embed::__private::Entry =
+                ::src_embed::__private::Entry::new(#name, #kind, SOURCE);
+            #[used]
+            #[cfg_attr(
+                any(
+                    target_os = "linux",
+                    target_os = "android",
+                    target_os = "freebsd",
+                    target_os = "netbsd",
+                    target_os = "openbsd",
+                    target_os = "dragonfly",
+                    target_os = "illumos",
+                ),
+                unsafe(link_section = ".init_array")
+            )]
+            #[cfg_attr(
+                any(target_os = "macos", target_os = "ios"),
+                unsafe(link_section = "__DATA,__mod_init_func")
+            )]
+            #[cfg_attr(windows, unsafe(link_section = ".CRT$XCU"))]
+            static REGISTER: extern "C" fn() = {
+                extern "C" fn register() {
+                    ::src_embed::__private::submit(&ENTRY);
+                }
+                register
+            };
+            SOURCE
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Structured metadata constants emitted next to the source constant.
+//!
+//! These describe parts of the item that tooling would otherwise have to
+//! recover by parsing the embedded source text.
+
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{FnArg, GenericParam, Item, ReturnType};
+
+use crate::{naming, pretty};
+
+/// Generate the metadata constants for `item`.
+///
+/// `attrs` are the attributes shared by every generated constant.
+pub fn expand(item: &Item, item_name: &str, attrs: &TokenStream) -> TokenStream {
+    match item {
+        Item::Fn(fn_item) => {
+            let qualifiers = fn_qualifiers(&fn_item.sig);
+            let qualifiers_ident = const_ident(item_name, "QUALIFIERS");
+            let params = fn_params(&fn_item.sig);
+            let params_ident = const_ident(item_name, "PARAMS");
+            let return_type = fn_return_type(&fn_item.sig);
+            let return_ident = const_ident(item_name, "RETURN");
+            quote! {
+                #attrs
+                pub const #qualifiers_ident: &[&str] = &[#(#qualifiers),*];
+                #attrs
+                pub const #params_ident: &[&str] = &[#(#params),*];
+                #attrs
+                pub const #return_ident: &str = #return_type;
+            }
+        }
+        Item::Impl(impl_item) => {
+            let (names, values): (Vec<String>, Vec<String>) = impl_item
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    syn::ImplItem::Const(item) => {
+                        Some((item.ident.to_string(), render(&item.expr)))
+                    }
+                    _ => None,
+                })
+                .unzip();
+            let ident = const_ident(item_name, "ASSOC_CONSTS");
+            let args = impl_args(&impl_item.self_ty);
+            let args_ident = const_ident(item_name, "IMPL_ARGS");
+            quote! {
+                #attrs
+                pub const #ident: &[(&str, &str)] = &[#((#names, #values)),*];
+                #attrs
+                pub const #args_ident: &[&str] = &[#(#args),*];
+            }
+        }
+        Item::Trait(trait_item) => {
+            let generic_defaults = generic_defaults_const(&trait_item.generics, item_name, attrs);
+            let (names, bodies): (Vec<String>, Vec<String>) = trait_item
+                .items
+                .iter()
+                .filter_map(|item| match item {
+                    syn::TraitItem::Fn(item) => {
+                        Some((item.sig.ident.to_string(), render(item.default.as_ref()?)))
+                    }
+                    _ => None,
+                })
+                .unzip();
+            let ident = const_ident(item_name, "DEFAULTS");
+            quote! {
+                #generic_defaults
+                #attrs
+                pub const #ident: &[(&str, &str)] = &[#((#names, #bodies)),*];
+            }
+        }
+        Item::Struct(syn::ItemStruct { generics, .. })
+        | Item::Enum(syn::ItemEnum { generics, .. })
+        | Item::Union(syn::ItemUnion { generics, .. })
+        | Item::Type(syn::ItemType { generics, .. }) => {
+            generic_defaults_const(generics, item_name, attrs)
+        }
+        _ => TokenStream::new(),
+    }
+}
+
+/// Generate `__FOO_GENERIC_DEFAULTS__` for an item with `generics`.
+fn generic_defaults_const(
+    generics: &syn::Generics,
+    item_name: &str,
+    attrs: &TokenStream,
+) -> TokenStream {
+    let (names, defaults): (Vec<String>, Vec<String>) =
+        generic_defaults(generics).into_iter().unzip();
+    let ident = const_ident(item_name, "GENERIC_DEFAULTS");
+    quote! {
+        #attrs
+        pub const #ident: &[(&str, &str)] = &[#((#names, #defaults)),*];
+    }
+}
+
+/// The name and default of each generic parameter that has one, in source
+/// order: the type of a type parameter, the expression of a const one.
+fn generic_defaults(generics: &syn::Generics) -> Vec<(String, String)> {
+    generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            GenericParam::Type(param) => {
+                Some((param.ident.to_string(), render(param.default.as_ref()?)))
+            }
+            GenericParam::Const(param) => {
+                Some((param.ident.to_string(), render(param.default.as_ref()?)))
+            }
+            GenericParam::Lifetime(_) => None,
+        })
+        .collect()
+}
+
+/// The generic arguments of the last segment of an impl's target type, such
+/// as `u32` and `String` for `Foo<u32, String>`. Targets that are not a
+/// path, like references and tuples, have none.
+fn impl_args(self_ty: &syn::Type) -> Vec<String> {
+    let syn::Type::Path(path) = self_ty else {
+        return Vec::new();
+    };
+    match path.path.segments.last().map(|segment| &segment.arguments) {
+        Some(syn::PathArguments::AngleBracketed(args)) => args.args.iter().map(render).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The qualifiers of a function signature, in source order: `const`,
+/// `async`, `unsafe` and the `extern` ABI.
+fn fn_qualifiers(sig: &syn::Signature) -> Vec<String> {
+    let mut qualifiers = Vec::new();
+    if sig.constness.is_some() {
+        qualifiers.push("const".to_string());
+    }
+    if sig.asyncness.is_some() {
+        qualifiers.push("async".to_string());
+    }
+    if sig.unsafety.is_some() {
+        qualifiers.push("unsafe".to_string());
+    }
+    if let Some(abi) = &sig.abi {
+        qualifiers.push(match &abi.name {
+            Some(name) => format!("extern {}", name.token()),
+            None => "extern".to_string(),
+        });
+    }
+    qualifiers
+}
+
+/// Each parameter of a function signature as written, receiver included,
+/// without its attributes.
+fn fn_params(sig: &syn::Signature) -> Vec<String> {
+    sig.inputs
+        .iter()
+        .map(|param| {
+            let mut param = param.clone();
+            match &mut param {
+                FnArg::Receiver(receiver) => receiver.attrs.clear(),
+                FnArg::Typed(typed) => typed.attrs.clear(),
+            }
+            render(&param)
+        })
+        .collect()
+}
+
+/// The return type of a function signature, `()` when it is left out.
+fn fn_return_type(sig: &syn::Signature) -> String {
+    match &sig.output {
+        ReturnType::Default => "()".to_string(),
+        ReturnType::Type(_, ty) => render(ty),
+    }
+}
+
+fn render(tokens: &impl ToTokens) -> String {
+    pretty::inline(&tokens.to_token_stream())
+}
+
+fn const_ident(item_name: &str, suffix: &str) -> syn::Ident {
+    syn::Ident::new(&naming::const_name(item_name, suffix), Span::call_site())
+}
@@ -0,0 +1,142 @@
+//! Name derivation for the generated constants.
+//!
+//! Both the attribute macro and `src_embed::scan_source` go through these
+//! functions, so a constant name predicted from source text always matches the
+//! one the macro emits.
+
+use syn::ext::IdentExt;
+use syn::{Item, Type};
+
+use crate::{Case, Options};
+
+/// Derive the name segment of the generated constants of `item`, in the
+/// case requested by `case = "..."`.
+///
+/// A `name = "..."` argument always takes precedence over the name derived
+/// from the item, including one built with `full_path`.
+pub fn item_name(item: &Item, options: &Options) -> String {
+    apply_case(&display_name(item, options), options.case)
+}
+
+/// The name `item` is listed under in the manifest: the name a macro
+/// chose for it, the `name = "..."` argument or the name derived from the
+/// item, before any case conversion.
+pub fn display_name(item: &Item, options: &Options) -> String {
+    match options.generated_name.as_ref().or(options.name.as_ref()) {
+        Some(name) => name.clone(),
+        None => derived_name(item, options),
+    }
+}
+
+/// The name `src_embed_trait_impls!` gives `impl_item`, an impl of the
+/// trait shown as `trait_name`, unless it has a `name = "..."`:
+/// `Shape_for_Circle`.
+pub fn trait_impl_name(trait_name: &str, impl_item: &Item, options: &Options) -> String {
+    format!("{}_for_{}", trait_name, display_name(impl_item, options))
+}
+
+/// The name `nested` gives `item`, declared in the body of the function
+/// shown as `parent`: `outer_double`.
+pub fn nested_name(parent: &str, item: &Item, options: &Options) -> String {
+    format!("{}_{}", parent, derived_name(item, options))
+}
+
+/// The keyword naming the kind of `item`, such as `struct` or `impl`.
+pub fn kind(item: &Item) -> &'static str {
+    match item {
+        Item::Const(_) => "const",
+        Item::Enum(_) => "enum",
+        Item::ExternCrate(_) => "extern crate",
+        Item::Fn(_) => "fn",
+        Item::ForeignMod(_) => "extern",
+        Item::Impl(_) => "impl",
+        Item::Macro(_) => "macro",
+        Item::Mod(_) => "mod",
+        Item::Static(_) => "static",
+        Item::Struct(_) => "struct",
+        Item::Trait(_) | Item::TraitAlias(_) => "trait",
+        Item::Type(_) => "type",
+        Item::Union(_) => "union",
+        Item::Use(_) => "use",
+        _ => "item",
+    }
+}
+
+/// The name of `item` itself, as written but without an `r#` prefix.
+fn derived_name(item: &Item, options: &Options) -> String {
+    match item {
+        Item::Trait(trait_item) => trait_item.ident.unraw().to_string(),
+        Item::Impl(impl_item) => {
+            // For impl blocks, extract the type being implemented for
+            if let Type::Path(type_path) = &*impl_item.self_ty {
+                let segments = &type_path.path.segments;
+                if options.full_path && !segments.is_empty() {
+                    segments
+                        .iter()
+                        .map(|seg| seg.ident.unraw().to_string())
+                        .collect::<Vec<_>>()
+                        .join("_")
+                } else {
+                    segments
+                        .last()
+                        .map(|seg| seg.ident.unraw().to_string())
+                        .unwrap_or_else(|| "UNKNOWN".to_string())
+                }
+            } else {
+                "UNKNOWN".to_string()
+            }
+        }
+        Item::Struct(struct_item) => struct_item.ident.unraw().to_string(),
+        Item::Enum(enum_item) => enum_item.ident.unraw().to_string(),
+        Item::Fn(fn_item) => fn_item.sig.ident.unraw().to_string(),
+        _ => "ITEM".to_string(),
+    }
+}
+
+/// Convert an item name to `case`.
+pub fn apply_case(name: &str, case: Case) -> String {
+    match case {
+        Case::Upper => name.to_uppercase(),
+        Case::Lower => name.to_lowercase(),
+        Case::Preserve => name.to_string(),
+        Case::ScreamingSnake => screaming_snake(name),
+    }
+}
+
+/// `FooBar`, `fooBar` and `foo_bar` all become `FOO_BAR`; a run of capitals
+/// is one word, so `HTTPServer` becomes `HTTP_SERVER`.
+fn screaming_snake(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, &ch) in chars.iter().enumerate() {
+        if ch.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                out.push('_');
+            }
+        }
+        out.extend(ch.to_uppercase());
+    }
+    out
+}
+
+/// The name of the source constant for an item named `item_name`:
+/// `__ITEMNAME_SOURCE__`.
+pub fn source_const_name(item_name: &str) -> String {
+    const_name(item_name, "SOURCE")
+}
+
+/// The name of a generated function for an item named `item_name`:
+/// `__itemname_suffix`.
+pub fn fn_name(item_name: &str, suffix: &str) -> String {
+    format!("__{}_{}", item_name.to_lowercase(), suffix)
+}
+
+/// The name of a generated constant for an item named `item_name`:
+/// `__ITEMNAME_SUFFIX__`.
+///
+/// `item_name` is used as given, already converted by [`item_name`].
+pub fn const_name(item_name: &str, suffix: &str) -> String {
+    format!("__{}_{}__", item_name, suffix)
+}
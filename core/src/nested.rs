@@ -0,0 +1,59 @@
+//! `nested`: embedding the items defined inside a function's body.
+
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use syn::parse::{ParseStream, Parser};
+use syn::{Item, Stmt};
+
+use crate::{items, naming, Options};
+
+/// Generate the constants of every item declared directly in the body of
+/// the function `item`, whose tokens are `input`.
+///
+/// Each nested item is embedded with the options of the function, named
+/// after the function and the item together: `fn inner` in `fn outer`
+/// becomes `__OUTER_INNER_SOURCE__`. The items stay where they are.
+pub fn expand(options: &Options, input: &TokenStream, item: &Item) -> syn::Result<TokenStream> {
+    if !matches!(item, Item::Fn(_)) {
+        return Err(syn::Error::new_spanned(
+            item,
+            "`nested` only applies to functions",
+        ));
+    }
+    let Some(TokenTree::Group(body)) = input.clone().into_iter().last() else {
+        return Ok(TokenStream::new());
+    };
+    if body.delimiter() != Delimiter::Brace {
+        return Ok(TokenStream::new());
+    }
+
+    let parent = naming::display_name(item, options);
+    let mut consts = TokenStream::new();
+    for (nested, tokens) in body_items.parse2(body.stream())? {
+        let nested_options = Options {
+            generated_name: Some(naming::nested_name(&parent, &nested, options)),
+            nested_item: true,
+            ..options.clone()
+        };
+        let (nested_consts, _) = crate::embed(&nested_options, tokens, None)?;
+        consts.extend(nested_consts);
+    }
+    Ok(consts)
+}
+
+/// The items among the statements of a function body, with their tokens.
+fn body_items(input: ParseStream) -> syn::Result<Vec<(Item, TokenStream)>> {
+    input.call(syn::Attribute::parse_inner)?;
+    let mut found = Vec::new();
+    while !input.is_empty() {
+        match items::parse_with_tokens::<Stmt>(input) {
+            Ok((Stmt::Item(item), tokens)) => found.push((item, tokens.into_iter().collect())),
+            Ok(_) => {}
+            // Only the trailing expression of a body fails to parse as a
+            // statement, and no item follows it.
+            Err(_) => {
+                input.parse::<TokenStream>()?;
+            }
+        }
+    }
+    Ok(found)
+}
@@ -0,0 +1,417 @@
+//! Arguments accepted by `#[src_embed(...)]`.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+/// Arguments accepted by `#[src_embed(...)]`.
+#[derive(Clone, Default)]
+pub struct Options {
+    /// Name to use for the generated constants instead of the derived one.
+    pub name: Option<String>,
+    /// Case of the item name in the generated constant names.
+    pub case: Case,
+    /// Feature of the calling crate that gates the generated constant.
+    pub feature: Option<syn::LitStr>,
+    /// Name `impl` blocks after the full path of the implemented type.
+    pub full_path: bool,
+    /// Generated comment line prepended to the embedded source.
+    pub header: Option<Header>,
+    /// Emit the source as a `static` instead of a `const`.
+    pub as_static: bool,
+    /// Capture the item's text byte for byte as written in its source file.
+    pub raw: bool,
+    /// Shift a raw capture so the item starts at column zero.
+    pub reindent: bool,
+    /// Capture the item's tokens laid out by the built-in formatter.
+    pub pretty: bool,
+    /// Emit the identifiers of the item by category, as JSON.
+    pub highlight: bool,
+    /// Emit a function returning the source as a `Cow<'static, str>`.
+    pub cow: bool,
+    /// Collect the constants of a module's items into one module per kind.
+    pub group_by_kind: bool,
+    /// Embed only the `pub` items of an inherent `impl` block.
+    pub public_only: bool,
+    /// Embed only the declaration of a struct or enum, with a field or
+    /// variant count in place of its body.
+    pub header_only: bool,
+    /// Emit the edition and compiler the item was embedded with.
+    pub build_info: bool,
+    /// Edition reported by `build_info` instead of the crate's own.
+    pub edition: Option<syn::LitStr>,
+    /// Also emit the source as a NUL-terminated `&CStr`.
+    pub cstr: bool,
+    /// Language of the embedded snippet, `rust` unless given.
+    pub lang: Option<String>,
+    /// Remove trailing commas from the embedded source.
+    pub normalize_commas: bool,
+    /// Build context noted in a comment line above the embedded source.
+    pub cfg_note: Option<String>,
+    /// Embed the formatted item with a trailing newline, for snapshots.
+    pub golden: bool,
+    /// Also embed the items declared in the body of a function.
+    pub nested: bool,
+    /// Leave the bodies of macro invocations out of the embedded source.
+    pub strip_macros: bool,
+    /// Leave the dead-code lint on for the generated constants, through
+    /// `allow_dead_code = false`.
+    pub dead_code_lint: bool,
+    /// Leave `#[doc(hidden)]` off the generated constants.
+    pub visible_docs: bool,
+    /// Width of the tab stops that leading tabs of a raw capture expand to.
+    pub tabs: Option<usize>,
+    /// Embed the source with the names the item declares replaced by
+    /// generic ones.
+    pub anonymize: bool,
+    /// File, relative to `CARGO_MANIFEST_DIR`, the embedded source must
+    /// equal.
+    pub sync_check: Option<syn::LitStr>,
+    /// Name for the generated constants chosen by a macro or argument that
+    /// embeds several items, `src_embed_trait_impls!` or `nested`. It is not
+    /// an argument itself, so `to_args` leaves it out.
+    pub generated_name: Option<String>,
+    /// The item was found by `nested` in the body of a function, whose
+    /// arguments it shares: it is not searched for nested items itself and
+    /// not compared by `sync_check`.
+    pub nested_item: bool,
+}
+
+/// Cases accepted by `case = "..."` for the item name in constant names.
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum Case {
+    /// `FooBar` becomes `FOOBAR`.
+    #[default]
+    Upper,
+    /// `FooBar` becomes `foobar`.
+    Lower,
+    /// `FooBar` stays `FooBar`.
+    Preserve,
+    /// `FooBar` becomes `FOO_BAR`.
+    ScreamingSnake,
+}
+
+impl Case {
+    /// The value of `case = "..."` selecting this case.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Case::Upper => "upper",
+            Case::Lower => "lower",
+            Case::Preserve => "preserve",
+            Case::ScreamingSnake => "screaming_snake",
+        }
+    }
+}
+
+/// Kinds of header line accepted by `header = "..."`.
+#[derive(Clone)]
+pub enum Header {
+    /// `// sha: <hex>` with the SHA-256 of the source that follows it.
+    Checksum,
+}
+
+impl Options {
+    /// Parse the argument list of a `#[src_embed(...)]` attribute.
+    pub fn from_args(args: TokenStream) -> syn::Result<Self> {
+        use syn::parse::Parser;
+
+        let mut options = Options::default();
+        let mut count = 0;
+        syn::meta::parser(|meta| {
+            count += 1;
+            options.parse(meta)
+        })
+        .parse2(args)?;
+        options.check(count)?;
+        Ok(options)
+    }
+
+    /// Parse the arguments of an already parsed `#[src_embed(...)]` attribute.
+    pub fn from_attribute(attr: &syn::Attribute) -> syn::Result<Self> {
+        let mut options = Options::default();
+        let mut count = 0;
+        if !matches!(attr.meta, syn::Meta::Path(_)) {
+            attr.parse_nested_meta(|meta| {
+                count += 1;
+                options.parse(meta)
+            })?;
+        }
+        options.check(count)?;
+        Ok(options)
+    }
+
+    /// Reject combinations of the `count` parsed arguments that make no sense.
+    fn check(&self, count: usize) -> syn::Result<()> {
+        if self.reindent && !self.raw {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`reindent` only applies to `raw` captures; add `raw`",
+            ));
+        }
+        if self.tabs.is_some() && !self.raw {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`tabs` only applies to `raw` captures; add `raw`",
+            ));
+        }
+        if self.public_only && self.raw {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`public_only` renders the item from its tokens and cannot be combined with `raw`",
+            ));
+        }
+        if self.golden && self.raw {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`golden` formats the item from its tokens and cannot be combined with `raw`",
+            ));
+        }
+        if self.strip_macros && self.raw {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`strip_macros` renders the item from its tokens and cannot be combined with `raw`",
+            ));
+        }
+        if self.header_only && (self.raw || self.pretty || self.golden || self.public_only) {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`header_only` replaces the captured body and cannot be combined with `raw`, \
+                 `pretty`, `golden` or `public_only`",
+            ));
+        }
+        if self.group_by_kind && count > 1 {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`group_by_kind` takes no other arguments; put them on the items of the module",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parse a single `key` or `key = value` argument.
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("name") {
+            let name: syn::LitStr = meta.value()?.parse()?;
+            let value = name.value();
+            // The name has to fit into the identifiers generated from it.
+            let ident = crate::naming::source_const_name(&value);
+            if value.is_empty() || syn::parse_str::<syn::Ident>(&ident).is_err() {
+                return Err(syn::Error::new(name.span(), "`name` must be an identifier"));
+            }
+            self.name = Some(value);
+            Ok(())
+        } else if meta.path.is_ident("case") {
+            let case: syn::LitStr = meta.value()?.parse()?;
+            self.case = match case.value().as_str() {
+                "upper" => Case::Upper,
+                "lower" => Case::Lower,
+                "preserve" => Case::Preserve,
+                "screaming_snake" => Case::ScreamingSnake,
+                _ => return Err(syn::Error::new(
+                    case.span(),
+                    "expected `\"upper\"`, `\"lower\"`, `\"preserve\"` or `\"screaming_snake\"`",
+                )),
+            };
+            Ok(())
+        } else if meta.path.is_ident("feature") {
+            self.feature = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("full_path") {
+            self.full_path = true;
+            Ok(())
+        } else if meta.path.is_ident("as_static") {
+            self.as_static = true;
+            Ok(())
+        } else if meta.path.is_ident("raw") {
+            self.raw = true;
+            Ok(())
+        } else if meta.path.is_ident("reindent") {
+            self.reindent = true;
+            Ok(())
+        } else if meta.path.is_ident("pretty") {
+            self.pretty = true;
+            Ok(())
+        } else if meta.path.is_ident("highlight") {
+            if cfg!(not(feature = "highlight")) {
+                return Err(meta.error("`highlight` requires the `highlight` feature of src_embed"));
+            }
+            self.highlight = true;
+            Ok(())
+        } else if meta.path.is_ident("cow") {
+            if cfg!(not(feature = "std")) {
+                return Err(meta.error("`cow` requires the `std` feature of src_embed"));
+            }
+            self.cow = true;
+            Ok(())
+        } else if meta.path.is_ident("group_by_kind") {
+            self.group_by_kind = true;
+            Ok(())
+        } else if meta.path.is_ident("public_only") {
+            self.public_only = true;
+            Ok(())
+        } else if meta.path.is_ident("header_only") {
+            self.header_only = true;
+            Ok(())
+        } else if meta.path.is_ident("build_info") {
+            self.build_info = true;
+            Ok(())
+        } else if meta.path.is_ident("edition") {
+            let edition: syn::LitStr = meta.value()?.parse()?;
+            if !["2015", "2018", "2021", "2024"].contains(&edition.value().as_str()) {
+                return Err(syn::Error::new(
+                    edition.span(),
+                    "expected `\"2015\"`, `\"2018\"`, `\"2021\"` or `\"2024\"`",
+                ));
+            }
+            self.edition = Some(edition);
+            Ok(())
+        } else if meta.path.is_ident("cstr") {
+            self.cstr = true;
+            Ok(())
+        } else if meta.path.is_ident("lang") {
+            let lang: syn::LitStr = meta.value()?.parse()?;
+            if lang.value().trim().is_empty() {
+                return Err(syn::Error::new(lang.span(), "`lang` must not be empty"));
+            }
+            self.lang = Some(lang.value());
+            Ok(())
+        } else if meta.path.is_ident("normalize_commas") {
+            self.normalize_commas = true;
+            Ok(())
+        } else if meta.path.is_ident("cfg_note") {
+            let note: syn::LitStr = meta.value()?.parse()?;
+            let value = note.value();
+            if value.trim().is_empty() || value.contains(['\n', '\r']) {
+                return Err(syn::Error::new(
+                    note.span(),
+                    "`cfg_note` must be a single non-empty line",
+                ));
+            }
+            self.cfg_note = Some(value);
+            Ok(())
+        } else if meta.path.is_ident("golden") {
+            self.golden = true;
+            Ok(())
+        } else if meta.path.is_ident("nested") {
+            self.nested = true;
+            Ok(())
+        } else if meta.path.is_ident("strip_macros") {
+            self.strip_macros = true;
+            Ok(())
+        } else if meta.path.is_ident("allow_dead_code") {
+            let allow: syn::LitBool = meta.value()?.parse()?;
+            self.dead_code_lint = !allow.value;
+            Ok(())
+        } else if meta.path.is_ident("visible_docs") {
+            self.visible_docs = true;
+            Ok(())
+        } else if meta.path.is_ident("tabs") {
+            let width: syn::LitInt = meta.value()?.parse()?;
+            match width.base10_parse::<usize>() {
+                Ok(width @ 1..=16) => self.tabs = Some(width),
+                _ => {
+                    return Err(syn::Error::new(
+                        width.span(),
+                        "`tabs` must be a width from 1 to 16",
+                    ))
+                }
+            }
+            Ok(())
+        } else if meta.path.is_ident("anonymize") {
+            self.anonymize = true;
+            Ok(())
+        } else if meta.path.is_ident("sync_check") {
+            self.sync_check = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("header") {
+            let kind: syn::LitStr = meta.value()?.parse()?;
+            match kind.value().as_str() {
+                "checksum" => self.header = Some(Header::Checksum),
+                _ => return Err(syn::Error::new(kind.span(), "expected `\"checksum\"`")),
+            }
+            Ok(())
+        } else {
+            Err(meta.error("unsupported src_embed argument"))
+        }
+    }
+
+    /// Render the options as an argument list that parses back to them.
+    ///
+    /// Arguments come in a fixed order and spelling, so equal options always
+    /// render the same way however they were written.
+    pub fn to_args(&self) -> String {
+        let mut args = Vec::new();
+        if let Some(name) = &self.name {
+            args.push(format!("name = {:?}", name));
+        }
+        if self.case != Case::Upper {
+            args.push(format!("case = {:?}", self.case.as_str()));
+        }
+        if let Some(feature) = &self.feature {
+            args.push(format!("feature = {:?}", feature.value()));
+        }
+        if let Some(edition) = &self.edition {
+            args.push(format!("edition = {:?}", edition.value()));
+        }
+        if let Some(lang) = &self.lang {
+            args.push(format!("lang = {:?}", lang));
+        }
+        if let Some(note) = &self.cfg_note {
+            args.push(format!("cfg_note = {:?}", note));
+        }
+        if self.dead_code_lint {
+            args.push("allow_dead_code = false".to_string());
+        }
+        if let Some(width) = self.tabs {
+            args.push(format!("tabs = {}", width));
+        }
+        if let Some(path) = &self.sync_check {
+            args.push(format!("sync_check = {:?}", path.value()));
+        }
+        let flags = [
+            (self.full_path, "full_path"),
+            (self.header.is_some(), "header = \"checksum\""),
+            (self.as_static, "as_static"),
+            (self.raw, "raw"),
+            (self.reindent, "reindent"),
+            (self.pretty, "pretty"),
+            (self.highlight, "highlight"),
+            (self.cow, "cow"),
+            (self.group_by_kind, "group_by_kind"),
+            (self.public_only, "public_only"),
+            (self.header_only, "header_only"),
+            (self.build_info, "build_info"),
+            (self.cstr, "cstr"),
+            (self.normalize_commas, "normalize_commas"),
+            (self.golden, "golden"),
+            (self.nested, "nested"),
+            (self.strip_macros, "strip_macros"),
+            (self.visible_docs, "visible_docs"),
+            (self.anonymize, "anonymize"),
+        ];
+        args.extend(
+            flags
+                .iter()
+                .filter(|(set, _)| *set)
+                .map(|(_, arg)| arg.to_string()),
+        );
+        args.join(", ")
+    }
+
+    /// Whether the item is laid out by the formatter, through `pretty` or
+    /// `golden`.
+    pub fn formatted(&self) -> bool {
+        self.pretty || self.golden
+    }
+
+    /// The language of the embedded snippet.
+    pub fn lang(&self) -> &str {
+        self.lang.as_deref().unwrap_or("rust")
+    }
+
+    /// The `cfg` predicate the generated constant is gated behind, if any.
+    pub fn cfg_predicate(&self) -> Option<TokenStream> {
+        self.feature
+            .as_ref()
+            .map(|feature| quote!(feature = #feature))
+    }
+}
@@ -0,0 +1,751 @@
+//! A small token-based pretty printer for the `pretty` capture.
+//!
+//! The printer lays the item out in the usual rustfmt shape: one statement,
+//! field, variant or match arm per line, four-space indentation inside
+//! braces, doc attributes written back as `///` comments, a `where` clause
+//! on lines of its own and a trailing comma after the last element of a
+//! multi-line list. It works on tokens alone, so spacing inside a line
+//! follows heuristics and long lines are not wrapped.
+//!
+//! Apart from those trailing commas, and the one ending a `where` clause,
+//! only whitespace is ever changed, and [`pretty`] checks that its output
+//! tokenizes back to the input before returning it.
+
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+
+const INDENT: &str = "    ";
+
+/// Braces whose contents render shorter than this stay on one line, unless
+/// they hold statements or belong to an item or control-flow construct.
+const INLINE_BRACES_WIDTH: usize = 40;
+
+/// Keywords whose braces always hold statements or items.
+const BLOCK_KEYWORDS: &[&str] = &[
+    "async",
+    "else",
+    "extern",
+    "fn",
+    "for",
+    "if",
+    "impl",
+    "loop",
+    "macro_rules",
+    "mod",
+    "trait",
+    "unsafe",
+    "while",
+];
+
+/// Keywords whose braces hold a comma-separated list.
+const LIST_KEYWORDS: &[&str] = &["enum", "match", "struct", "union"];
+
+/// Keywords after which a name is being defined, so a `<` opens its
+/// generics.
+const DEFINITIONS: &[&str] = &["fn", "struct", "enum", "trait", "type", "union"];
+
+/// Reserved words that are not used like ordinary identifiers.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "type", "union", "unsafe", "use", "where", "while",
+    "yield",
+];
+
+/// Operators made of several punctuation characters, longest first.
+const OPERATORS: &[&str] = &[
+    "<<=", ">>=", "...", "..=", "::", "->", "=>", "==", "!=", "<=", ">=", "&&", "||", "+=", "-=",
+    "*=", "/=", "%=", "^=", "&=", "|=", "<<", ">>", "..",
+];
+
+/// Lay `input` out over several lines.
+///
+/// Returns `None` if the laid out text does not have exactly the tokens of
+/// `input`.
+pub fn pretty(input: &TokenStream) -> Option<String> {
+    let tokens: Vec<TokenTree> = input.clone().into_iter().collect();
+    let mut writer = Writer::default();
+    writer.stream(&tokens, Mode::Statements);
+
+    let text = writer.out.trim_end().to_string();
+    let reparsed: TokenStream = text.parse().ok()?;
+    same_tokens(&reparsed, input).then_some(text)
+}
+
+/// Write a fragment such as a type on one line, with the spacing of
+/// [`pretty`].
+///
+/// Falls back to the compiler's rendering of the tokens if the result does
+/// not tokenize back to `input`.
+pub fn inline(input: &TokenStream) -> String {
+    let tokens: Vec<TokenTree> = input.clone().into_iter().collect();
+    let mut writer = Writer::default();
+    writer.stream(&tokens, Mode::Inline);
+
+    match writer.out.parse::<TokenStream>() {
+        Ok(reparsed) if same_tokens(&reparsed, input) => writer.out,
+        _ => input.to_string(),
+    }
+}
+
+/// How the tokens of a stream are spread over lines.
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    /// Everything stays on the current line.
+    Inline,
+    /// A new line after every `;`, attribute and braced block.
+    Statements,
+    /// A new line after every `,` and attribute. `arms` marks match arms,
+    /// which take no trailing comma after a braced block.
+    List { arms: bool },
+}
+
+/// What an operator does, as far as spacing is concerned.
+#[derive(Clone, Copy, PartialEq)]
+enum Role {
+    Binary,
+    Unary,
+    Path,
+    Dot,
+    Range,
+    Comma,
+    Semi,
+    Colon,
+    Question,
+    GenericOpen,
+    GenericClose,
+    ClosureOpen,
+    ClosureClose,
+    MacroBang,
+    Hash,
+    Dollar,
+}
+
+/// The last thing written on the current line.
+#[derive(Clone, PartialEq)]
+enum Prev {
+    Start,
+    Word(String),
+    Op(Role),
+    Open(Delimiter),
+    Close(Delimiter),
+}
+
+/// The next thing to be written.
+enum Next<'a> {
+    Word(&'a str),
+    Op(Role),
+    Open(Delimiter),
+    Close(Delimiter),
+}
+
+struct Writer {
+    out: String,
+    indent: usize,
+    prev: Prev,
+    prev2: Prev,
+    /// Generic argument lists currently open on this line.
+    generics: usize,
+    /// Between the `|`s of a closure's parameter list.
+    closure_params: bool,
+    /// The tokens after the `<` of the operator being written can close it
+    /// as the generic arguments of a type.
+    type_follows: bool,
+    /// Keywords written on the current line, used to classify braces.
+    line_keywords: Vec<String>,
+    /// The last thing written was the `}` of a multi-line block.
+    after_block: bool,
+}
+
+impl Default for Writer {
+    fn default() -> Self {
+        Writer {
+            out: String::new(),
+            indent: 0,
+            prev: Prev::Start,
+            prev2: Prev::Start,
+            generics: 0,
+            closure_params: false,
+            type_follows: false,
+            line_keywords: Vec::new(),
+            after_block: false,
+        }
+    }
+}
+
+impl Writer {
+    fn stream(&mut self, tokens: &[TokenTree], mode: Mode) {
+        let mut i = 0;
+        while i < tokens.len() {
+            match &tokens[i] {
+                TokenTree::Punct(punct) if punct.as_char() == '#' && mode != Mode::Inline => {
+                    if let Some(len) = attribute_len(&tokens[i..]) {
+                        if self.prev == Prev::Start {
+                            self.attribute(&tokens[i..i + len]);
+                            self.newline();
+                            i += len;
+                            continue;
+                        }
+                    }
+                    i += self.punct(tokens, i);
+                }
+                TokenTree::Punct(punct) => {
+                    let len = self.punct(tokens, i);
+                    // A joint run such as `>;` ends with the separator too.
+                    let ch = match &tokens[i + len - 1] {
+                        TokenTree::Punct(last) => last.as_char(),
+                        _ => punct.as_char(),
+                    };
+                    if mode != Mode::Inline {
+                        let ends_line = match mode {
+                            Mode::Statements => ch == ';',
+                            Mode::List { .. } => ch == ',',
+                            Mode::Inline => false,
+                        };
+                        if ends_line {
+                            self.newline();
+                        }
+                    }
+                    i += len;
+                }
+                TokenTree::Ident(ident) if ident == "where" && mode != Mode::Inline => {
+                    i += self.where_clause(&tokens[i..]);
+                }
+                TokenTree::Ident(ident) => {
+                    self.word(&ident.to_string());
+                    i += 1;
+                }
+                TokenTree::Literal(literal) => {
+                    self.word(&literal.to_string());
+                    i += 1;
+                }
+                TokenTree::Group(group) => {
+                    let contents: Vec<TokenTree> = group.stream().into_iter().collect();
+                    match group.delimiter() {
+                        Delimiter::None => self.stream(&contents, mode),
+                        Delimiter::Brace => {
+                            self.braces(&contents);
+                            if mode != Mode::Inline && !continues_line(tokens.get(i + 1)) {
+                                self.newline();
+                            }
+                        }
+                        delimiter => {
+                            self.write(Next::Open(delimiter), open(delimiter));
+                            self.stream(&contents, Mode::Inline);
+                            self.write(Next::Close(delimiter), close(delimiter));
+                        }
+                    }
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    fn braces(&mut self, contents: &[TokenTree]) {
+        if contents.is_empty() {
+            // rustfmt opens an empty block on its own line after a `where`
+            // clause and closes it on the next.
+            let text = if self.prev == Prev::Start {
+                "{\n}"
+            } else {
+                "{}"
+            };
+            self.write(Next::Open(Delimiter::Brace), text);
+            self.prev = Prev::Close(Delimiter::Brace);
+            self.after_block = true;
+            return;
+        }
+
+        let mode = self.brace_mode(contents);
+        if mode == Mode::Inline {
+            self.write(Next::Open(Delimiter::Brace), "{");
+            self.stream(contents, Mode::Inline);
+            // A list kept on one line has no trailing comma.
+            if self.out.ends_with(',') {
+                self.out.pop();
+            }
+            self.write(Next::Close(Delimiter::Brace), "}");
+            return;
+        }
+
+        self.write(Next::Open(Delimiter::Brace), "{");
+        let outer = (self.generics, self.closure_params);
+        self.newline();
+        self.indent += 1;
+        self.stream(contents, mode);
+        // The last element of a multi-line list gets a trailing comma, except
+        // for a match arm ending in a braced block.
+        if let Mode::List { arms } = mode {
+            let end = self.out.trim_end().len();
+            if !self.out[..end].ends_with(',') && !(arms && self.after_block) {
+                self.out.insert(end, ',');
+            }
+        }
+        self.newline();
+        self.indent -= 1;
+        (self.generics, self.closure_params) = outer;
+        self.write(Next::Close(Delimiter::Brace), "}");
+        self.after_block = true;
+    }
+
+    /// Write the `where` clause at the start of `tokens` as rustfmt does:
+    /// the keyword on a line of its own, then one predicate per line with a
+    /// trailing comma, and the body's brace on the next line. Before a `;`
+    /// the last predicate takes no comma. Returns how many tokens the clause
+    /// took up, up to the `{` or `;` that ends it.
+    fn where_clause(&mut self, tokens: &[TokenTree]) -> usize {
+        // The brace that follows is classified by the keywords of the line
+        // the clause is on.
+        let keywords = self.line_keywords.clone();
+        self.newline();
+        self.word("where");
+        self.newline();
+        self.indent += 1;
+
+        let mut len = 1;
+        let mut start = len;
+        let mut angles = 0usize;
+        while let Some(tt) = tokens.get(len) {
+            match tt {
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => break,
+                TokenTree::Punct(punct) if punct.as_char() == ';' => break,
+                TokenTree::Punct(punct) if punct.as_char() == ',' && angles == 0 => {
+                    self.stream(&tokens[start..=len], Mode::Inline);
+                    self.newline();
+                    start = len + 1;
+                }
+                TokenTree::Punct(punct) => {
+                    let arrow = matches!(
+                        tokens.get(len - 1),
+                        Some(TokenTree::Punct(prev)) if prev.as_char() == '-'
+                            && prev.spacing() == Spacing::Joint
+                    );
+                    match punct.as_char() {
+                        '<' => angles += 1,
+                        '>' if !arrow => angles = angles.saturating_sub(1),
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+            len += 1;
+        }
+        self.stream(&tokens[start..len], Mode::Inline);
+        let after_comma = start == len;
+
+        let ends_item = matches!(
+            tokens.get(len),
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ';'
+        );
+        if !after_comma && !ends_item {
+            self.op(",");
+        }
+        if !ends_item {
+            self.newline();
+        }
+        self.indent -= 1;
+        self.line_keywords = keywords;
+        len
+    }
+
+    fn brace_mode(&self, contents: &[TokenTree]) -> Mode {
+        let has_keyword = |keywords: &[&str]| {
+            self.line_keywords
+                .iter()
+                .any(|keyword| keywords.contains(&keyword.as_str()))
+        };
+        if has_keyword(&["match"]) {
+            return Mode::List { arms: true };
+        }
+        if has_keyword(LIST_KEYWORDS) {
+            return Mode::List { arms: false };
+        }
+        if has_keyword(BLOCK_KEYWORDS) {
+            return Mode::Statements;
+        }
+
+        let has_punct = |ch: char| {
+            contents
+                .iter()
+                .any(|tt| matches!(tt, TokenTree::Punct(p) if p.as_char() == ch))
+        };
+        if has_punct(';') {
+            return Mode::Statements;
+        }
+        let mut inline = Writer::default();
+        inline.stream(contents, Mode::Inline);
+        if inline.out.len() <= INLINE_BRACES_WIDTH && !inline.out.contains('\n') {
+            Mode::Inline
+        } else if has_punct(',') {
+            Mode::List { arms: false }
+        } else {
+            Mode::Statements
+        }
+    }
+
+    /// Write an outer or inner attribute, as a `///` or `//!` comment when it
+    /// is a single-line doc comment.
+    fn attribute(&mut self, tokens: &[TokenTree]) {
+        let inner = tokens.len() == 3;
+        let Some(TokenTree::Group(group)) = tokens.last() else {
+            return;
+        };
+        if let Some(doc) = doc_comment(&group.stream()) {
+            self.write(Next::Word(""), if inner { "//!" } else { "///" });
+            self.out.push_str(&doc);
+            return;
+        }
+
+        self.op("#");
+        if inner {
+            self.op("!");
+        }
+        let contents: Vec<TokenTree> = group.stream().into_iter().collect();
+        self.write(Next::Open(Delimiter::Bracket), "[");
+        self.stream(&contents, Mode::Inline);
+        self.write(Next::Close(Delimiter::Bracket), "]");
+    }
+
+    /// Write the operator starting at `tokens[i]` and return how many tokens
+    /// it took up.
+    fn punct(&mut self, tokens: &[TokenTree], i: usize) -> usize {
+        let mut chars = String::new();
+        let mut len = 0;
+        while let Some(TokenTree::Punct(punct)) = tokens.get(i + len) {
+            chars.push(punct.as_char());
+            len += 1;
+            if punct.spacing() == Spacing::Alone {
+                break;
+            }
+        }
+
+        if chars == "'" {
+            if let Some(TokenTree::Ident(ident)) = tokens.get(i + 1) {
+                self.word(&format!("'{}", ident));
+                return 2;
+            }
+        }
+
+        self.type_follows = chars
+            .find('<')
+            .is_some_and(|at| type_follows(&tokens[i + at + 1..]));
+        let mut rest = chars.as_str();
+        while !rest.is_empty() {
+            let op = OPERATORS
+                .iter()
+                .copied()
+                .find(|op| rest.starts_with(op))
+                .unwrap_or(&rest[..1]);
+            self.op(op);
+            rest = &rest[op.len()..];
+        }
+        len
+    }
+
+    fn op(&mut self, op: &str) {
+        if (op == ">>" && self.generics >= 2) || (op == "<<" && self.generic_opens()) {
+            self.op(&op[..1]);
+            self.op(&op[..1]);
+            return;
+        }
+
+        let role = match op {
+            "::" => Role::Path,
+            "." => Role::Dot,
+            ".." | "..=" | "..." => Role::Range,
+            "," => Role::Comma,
+            ";" => Role::Semi,
+            ":" => Role::Colon,
+            "?" if self.unary_position() => Role::Unary,
+            "?" => Role::Question,
+            "#" => Role::Hash,
+            "$" => Role::Dollar,
+            "<" if self.generic_opens() => Role::GenericOpen,
+            ">" if self.generics > 0 => Role::GenericClose,
+            "|" if self.closure_params => Role::ClosureClose,
+            "|" if self.unary_position() => Role::ClosureOpen,
+            "!" if self.prev == Prev::Op(Role::Hash) => Role::Hash,
+            "!" if matches!(&self.prev, Prev::Word(word) if !is_keyword(word)) => Role::MacroBang,
+            "&" | "&&" | "*" | "-" | "!" if self.unary_position() => Role::Unary,
+            _ => Role::Binary,
+        };
+
+        match role {
+            Role::GenericOpen => self.generics += 1,
+            Role::GenericClose => self.generics -= 1,
+            Role::ClosureOpen => self.closure_params = true,
+            Role::ClosureClose => self.closure_params = false,
+            Role::Semi => self.generics = 0,
+            _ => {}
+        }
+        self.write(Next::Op(role), op);
+    }
+
+    fn word(&mut self, word: &str) {
+        self.write(Next::Word(word), word);
+        if KEYWORDS.contains(&word) || BLOCK_KEYWORDS.contains(&word) {
+            self.line_keywords.push(word.to_string());
+        }
+    }
+
+    /// Whether a `<` written now opens generic arguments rather than
+    /// comparing.
+    fn generic_opens(&self) -> bool {
+        match &self.prev {
+            Prev::Word(word) if word == "impl" || word == "for" => true,
+            Prev::Word(word) if is_keyword(word) => false,
+            Prev::Word(word) => {
+                (word.starts_with(|c: char| c.is_ascii_uppercase()) && self.type_follows)
+                    || matches!(
+                        &self.prev2,
+                        Prev::Word(def) if DEFINITIONS.contains(&def.as_str())
+                    )
+            }
+            Prev::Close(_) | Prev::Op(Role::GenericClose | Role::Question) => false,
+            _ => true,
+        }
+    }
+
+    /// Whether an operator written now applies to what follows it only.
+    fn unary_position(&self) -> bool {
+        match &self.prev {
+            Prev::Word(word) => is_keyword(word),
+            Prev::Close(_) => false,
+            Prev::Op(role) => !matches!(role, Role::GenericClose | Role::Question),
+            Prev::Start | Prev::Open(_) => true,
+        }
+    }
+
+    fn write(&mut self, next: Next, text: &str) {
+        if self.prev == Prev::Start {
+            for _ in 0..self.indent {
+                self.out.push_str(INDENT);
+            }
+        } else if space_between(&self.prev, &next) {
+            self.out.push(' ');
+        }
+        self.out.push_str(text);
+
+        self.prev2 = std::mem::replace(
+            &mut self.prev,
+            match next {
+                Next::Word(word) => Prev::Word(word.to_string()),
+                Next::Op(role) => Prev::Op(role),
+                Next::Open(delimiter) => Prev::Open(delimiter),
+                Next::Close(delimiter) => Prev::Close(delimiter),
+            },
+        );
+        self.after_block = false;
+    }
+
+    fn newline(&mut self) {
+        if self.prev != Prev::Start {
+            self.out.push('\n');
+            self.prev = Prev::Start;
+            self.prev2 = Prev::Start;
+            self.line_keywords.clear();
+            self.generics = 0;
+        }
+    }
+}
+
+fn space_between(prev: &Prev, next: &Next) -> bool {
+    use Role::*;
+
+    match (prev, next) {
+        (Prev::Start, _) => false,
+        (Prev::Open(Delimiter::Brace), _) | (_, Next::Close(Delimiter::Brace)) => true,
+        (Prev::Open(_), _) | (_, Next::Close(_)) => false,
+        (
+            _,
+            Next::Op(
+                Comma | Semi | Colon | Dot | Question | GenericOpen | GenericClose | ClosureClose
+                | MacroBang,
+            ),
+        ) => false,
+        (Prev::Op(MacroBang), Next::Open(delimiter)) => *delimiter == Delimiter::Brace,
+        (Prev::Op(Unary | Path | Dot | Range | GenericOpen | ClosureOpen | Hash | Dollar), _) => {
+            false
+        }
+        (_, Next::Op(Range)) => !matches!(prev, Prev::Word(_) | Prev::Close(_)),
+        (_, Next::Op(Path)) => match prev {
+            Prev::Word(word) => is_keyword(word),
+            Prev::Op(role) => matches!(role, Binary | Comma | Semi | Colon | ClosureClose),
+            _ => false,
+        },
+        (Prev::Word(word), Next::Open(Delimiter::Parenthesis | Delimiter::Bracket)) => {
+            (is_keyword(word) && word != "pub") || word.starts_with('\'')
+        }
+        (
+            Prev::Close(_) | Prev::Op(GenericClose | Question),
+            Next::Open(Delimiter::Parenthesis | Delimiter::Bracket),
+        ) => false,
+        _ => true,
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    KEYWORDS.contains(&word)
+}
+
+/// Whether `tokens`, which follow a `<`, reach the `>` that closes it as
+/// generic arguments would, rather than continuing a comparison such as
+/// `N < M` or `X < LIMIT && ok`.
+fn type_follows(tokens: &[TokenTree]) -> bool {
+    /// Keywords that can appear in a type or its generic arguments.
+    const TYPE_KEYWORDS: &[&str] = &[
+        "as", "const", "dyn", "extern", "fn", "for", "impl", "mut", "unsafe",
+    ];
+
+    let mut depth = 1;
+    for (i, tt) in tokens.iter().enumerate() {
+        let after_punct = i == 0 || matches!(tokens[i - 1], TokenTree::Punct(_));
+        let joint_with = |ch: char| {
+            matches!(tt, TokenTree::Punct(punct) if punct.spacing() == Spacing::Joint)
+                && matches!(tokens.get(i + 1), Some(TokenTree::Punct(next)) if next.as_char() == ch)
+        };
+        match tt {
+            TokenTree::Ident(ident) => {
+                let word = ident.to_string();
+                let lifetime = matches!(
+                    tokens.get(i.wrapping_sub(1)),
+                    Some(TokenTree::Punct(prev)) if prev.as_char() == '\''
+                );
+                if !lifetime && is_keyword(&word) && !TYPE_KEYWORDS.contains(&word.as_str()) {
+                    return false;
+                }
+            }
+            TokenTree::Literal(_) => {}
+            // A block is a const generic argument, such as `{ N + 1 }`.
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                if !after_punct {
+                    return false;
+                }
+            }
+            TokenTree::Group(_) => {}
+            TokenTree::Punct(punct) => match punct.as_char() {
+                '<' => depth += 1,
+                '>' if joint_with('=') => return false,
+                '>' => {
+                    // The `>` of `->` closes nothing.
+                    let arrow = matches!(
+                        tokens.get(i.wrapping_sub(1)),
+                        Some(TokenTree::Punct(prev)) if prev.as_char() == '-'
+                            && prev.spacing() == Spacing::Joint
+                    );
+                    if !arrow {
+                        depth -= 1;
+                        if depth == 0 {
+                            return true;
+                        }
+                    }
+                }
+                '-' if joint_with('>') => {}
+                '=' | '!' if joint_with('=') => return false,
+                ',' | ':' | '\'' | '=' | '+' | '?' | '!' => {}
+                // References and raw pointers, not `&&` or `*` between
+                // values.
+                '&' | '*' if after_punct => {}
+                _ => return false,
+            },
+        }
+    }
+    false
+}
+
+/// Whether the token after a braced block keeps it on the same line, as in
+/// `} else {` or `};`.
+fn continues_line(next: Option<&TokenTree>) -> bool {
+    match next {
+        Some(TokenTree::Punct(punct)) => punct.as_char() != '#',
+        Some(TokenTree::Ident(ident)) => ident == "else" || ident == "as",
+        _ => false,
+    }
+}
+
+/// The number of tokens in the `#[...]` or `#![...]` attribute at the start
+/// of `tokens`.
+fn attribute_len(tokens: &[TokenTree]) -> Option<usize> {
+    let bracketed = |tt: Option<&TokenTree>| match tt {
+        Some(TokenTree::Group(group)) => group.delimiter() == Delimiter::Bracket,
+        _ => false,
+    };
+    match tokens.get(1) {
+        Some(TokenTree::Punct(bang)) if bang.as_char() == '!' && bracketed(tokens.get(2)) => {
+            Some(3)
+        }
+        tt if bracketed(tt) => Some(2),
+        _ => None,
+    }
+}
+
+/// The text of a single-line `doc = "..."` attribute.
+fn doc_comment(contents: &TokenStream) -> Option<String> {
+    let meta: syn::MetaNameValue = syn::parse2(contents.clone()).ok()?;
+    if !meta.path.is_ident("doc") {
+        return None;
+    }
+    match meta.value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(text),
+            ..
+        }) => Some(text.value()).filter(|text| !text.contains('\n')),
+        _ => None,
+    }
+}
+
+fn open(delimiter: Delimiter) -> &'static str {
+    match delimiter {
+        Delimiter::Parenthesis => "(",
+        Delimiter::Bracket => "[",
+        Delimiter::Brace => "{",
+        Delimiter::None => "",
+    }
+}
+
+fn close(delimiter: Delimiter) -> &'static str {
+    match delimiter {
+        Delimiter::Parenthesis => ")",
+        Delimiter::Bracket => "]",
+        Delimiter::Brace => "}",
+        Delimiter::None => "",
+    }
+}
+
+/// Whether two streams hold the same tokens, ignoring spacing, the
+/// invisible groups left behind by `macro_rules!` substitutions and a
+/// trailing comma before a brace, which ends a list or a `where` clause.
+fn same_tokens(a: &TokenStream, b: &TokenStream) -> bool {
+    fn flatten(stream: &TokenStream, out: &mut Vec<String>) {
+        for tt in stream.clone() {
+            match tt {
+                TokenTree::Group(group) => {
+                    out.push(open(group.delimiter()).to_string());
+                    flatten(&group.stream(), out);
+                    out.push(close(group.delimiter()).to_string());
+                }
+                TokenTree::Punct(punct) => out.push(punct.as_char().to_string()),
+                other => out.push(other.to_string()),
+            }
+        }
+    }
+
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    flatten(a, &mut left);
+    flatten(b, &mut right);
+    let strip = |tokens: Vec<String>| {
+        let tokens: Vec<String> = tokens
+            .into_iter()
+            .filter(|token| !token.is_empty())
+            .collect();
+        (0..tokens.len())
+            .filter(|&i| {
+                !(tokens[i] == ","
+                    && tokens
+                        .get(i + 1)
+                        .is_some_and(|next| next == "}" || next == "{"))
+            })
+            .map(|i| tokens[i].clone())
+            .collect::<Vec<_>>()
+    };
+    strip(left) == strip(right)
+}
@@ -0,0 +1,87 @@
+//! `public_only`: the public API of an `impl` block.
+
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
+use syn::parse::{ParseStream, Parser};
+use syn::{ImplItem, Visibility};
+
+use crate::{capture, items, pretty, Options};
+
+/// Capture the `impl` block `input` with only its `pub` items.
+///
+/// Every item of a trait impl is as public as the trait, so those are
+/// captured whole. Inherent impl items are kept with their original tokens,
+/// and macro invocations are dropped since what they expand to is unknown.
+/// The block is rendered from its tokens, or laid out with `pretty`.
+pub fn capture_sources(
+    options: &Options,
+    input: &TokenStream,
+) -> syn::Result<Vec<(&'static str, String)>> {
+    let item_impl: syn::ItemImpl = syn::parse2(input.clone()).map_err(|err| {
+        syn::Error::new(
+            err.span(),
+            "`public_only` can only be applied to an `impl` block",
+        )
+    })?;
+    if item_impl.trait_.is_some() {
+        return Ok(crate::capture_sources(options, input));
+    }
+
+    // An impl block ends in its braces; everything before them is kept.
+    let mut header: Vec<TokenTree> = input.clone().into_iter().collect();
+    let Some(TokenTree::Group(braces)) = header.pop() else {
+        unreachable!("an impl block ends in braces");
+    };
+    let header: TokenStream = header.into_iter().collect();
+    let (inner_attrs, public) = (|input: ParseStream| {
+        let inner_attrs = input.call(syn::Attribute::parse_inner)?;
+        let mut public = Vec::new();
+        while !input.is_empty() {
+            let (item, tokens) = items::parse_with_tokens::<ImplItem>(input)?;
+            if is_public(&item) {
+                public.push(tokens.into_iter().collect::<TokenStream>());
+            }
+        }
+        Ok((inner_attrs, public))
+    })
+    .parse2(braces.stream())?;
+
+    let source = if options.formatted() {
+        let mut body = quote::quote!(#(#inner_attrs)*);
+        body.extend(public.iter().cloned());
+        let mut group = Group::new(Delimiter::Brace, body);
+        group.set_span(braces.span());
+        let mut tokens = match capture::written(&header) {
+            Some(written) => written.tokens(),
+            None => header.clone(),
+        };
+        tokens.extend([TokenTree::Group(group)]);
+        pretty::pretty(&tokens).unwrap_or_else(|| capture::reflow(&tokens))
+    } else {
+        let mut text = match capture::written(&header) {
+            Some(written) => written.reflow(),
+            None => capture::reflow(&header),
+        };
+        let body: Vec<String> = inner_attrs
+            .iter()
+            .map(|attr| quote::ToTokens::to_token_stream(attr).to_string())
+            .chain(public.iter().map(capture::reflow))
+            .collect();
+        if body.is_empty() {
+            text.push_str(" {}");
+        } else {
+            text.push_str(&format!(" {{ {} }}", body.join(" ")));
+        }
+        text
+    };
+    Ok(vec![("SOURCE", source)])
+}
+
+fn is_public(item: &ImplItem) -> bool {
+    let vis = match item {
+        ImplItem::Const(item) => &item.vis,
+        ImplItem::Fn(item) => &item.vis,
+        ImplItem::Type(item) => &item.vis,
+        _ => return false,
+    };
+    matches!(vis, Visibility::Public(_))
+}
@@ -0,0 +1,98 @@
+//! `__FOO_TOKEN_SPANS__`: where each token of the item sits in its
+//! embedded source.
+//!
+//! The embedded text is built in several ways (token rendering, the source
+//! file, the formatter) and rewritten by passes such as `normalize_commas`
+//! and `anonymize`, none of which report positions, so the offsets are taken
+//! from the finished text: it is split into tokens the way the compiler
+//! splits the item, and each range is exactly where its token is written.
+
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+
+use crate::{lexer, naming};
+
+/// Generate `__FOO_TOKEN_SPANS__` for an item embedded as `source`.
+pub fn expand(source: &str, item_name: &str, const_attrs: &TokenStream) -> TokenStream {
+    let spans = token_ranges(source)
+        .into_iter()
+        .map(|(start, end)| quote!((#start, #end)));
+
+    let ident = syn::Ident::new(
+        &naming::const_name(item_name, "TOKEN_SPANS"),
+        Span::call_site(),
+    );
+    quote! {
+        #const_attrs
+        pub const #ident: &[(usize, usize)] = &[#(#spans),*];
+    }
+}
+
+/// The byte range of every token of `source` in order.
+///
+/// Each punctuation character is a token of its own, as are the delimiters
+/// of a group, and a lifetime is its quote and its name, as in a
+/// `TokenStream`. A doc comment is one token; other comments, such as the
+/// `header` and `cfg_note` lines, are not tokens.
+fn token_ranges(source: &str) -> Vec<(usize, usize)> {
+    let text = source.as_bytes();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < text.len() {
+        let end = if let Some(end) = lexer::comment_end(text, i) {
+            let comment = source[i..end].trim_end();
+            if is_doc_comment(comment) {
+                ranges.push((i, i + comment.len()));
+            }
+            i = end;
+            continue;
+        } else if text[i].is_ascii_whitespace() {
+            i += 1;
+            continue;
+        } else if let Some(end) = lexer::literal_end(text, i) {
+            end
+        } else if text[i..].starts_with(b"r#")
+            && text.get(i + 2).is_some_and(|&b| lexer::is_ident_start(b))
+        {
+            lexer::ident_end(text, i + 2)
+        } else if lexer::is_ident_start(text[i]) {
+            lexer::ident_end(text, i)
+        } else if text[i].is_ascii_digit() {
+            number_end(text, i)
+        } else {
+            i + source[i..].chars().next().map_or(1, char::len_utf8)
+        };
+        ranges.push((i, end));
+        i = end;
+    }
+    ranges
+}
+
+/// Whether `comment` is a doc comment rather than a plain one: `///`, `//!`,
+/// `/**` or `/*!`, but not `////` or `/***`.
+fn is_doc_comment(comment: &str) -> bool {
+    (comment.starts_with("///") && !comment.starts_with("////"))
+        || comment.starts_with("//!")
+        || (comment.starts_with("/**") && !comment.starts_with("/***") && comment != "/**/")
+        || comment.starts_with("/*!")
+}
+
+/// The offset after the number literal starting at `at`, with its fraction,
+/// exponent and suffix: `1_000`, `2.5e-3`, `0xffu8`.
+fn number_end(text: &[u8], at: usize) -> usize {
+    let mut end = lexer::ident_end(text, at);
+    // A fraction, but not a range such as `0..2` or a method call on an
+    // integer such as `1.max(2)`.
+    if text.get(end) == Some(&b'.') && text.get(end + 1).is_some_and(u8::is_ascii_digit) {
+        end = lexer::ident_end(text, end + 1);
+    }
+    let hex = text[at..].starts_with(b"0x");
+    if !hex
+        && matches!(text[end - 1], b'e' | b'E')
+        && matches!(text.get(end), Some(b'+' | b'-'))
+        && text.get(end + 1).is_some_and(u8::is_ascii_digit)
+    {
+        end = lexer::ident_end(text, end + 1);
+    }
+    end
+}
@@ -0,0 +1,87 @@
+//! `strip_macros`: the item with the bodies of its macro invocations left
+//! out.
+
+use std::collections::HashSet;
+
+use proc_macro2::{Group, Span, TokenStream, TokenTree};
+use syn::visit::{self, Visit};
+
+/// The identifier standing in for a macro body until the text is rendered.
+const MARKER: &str = "__src_embed_macro__";
+
+/// What a macro body is replaced with in the embedded text.
+const PLACEHOLDER: &str = "/* macro */";
+
+/// `input` with the tokens inside the delimiters of every macro invocation
+/// replaced by a marker.
+///
+/// The invocations are found by walking the parsed item, which tells a
+/// macro call from, say, `if !(a && b)`. Their bodies are then replaced in
+/// the original tokens, so everything else renders exactly as it would
+/// without `strip_macros`. The path, `!` and delimiters of each invocation
+/// stay; [`placeholders`] turns the markers into comments once the tokens
+/// are rendered.
+pub fn strip(input: &TokenStream) -> TokenStream {
+    let Ok(item) = syn::parse2::<syn::Item>(input.clone()) else {
+        return input.clone();
+    };
+    let mut names = MacroNames::default();
+    names.visit_item(&item);
+    replace_bodies(input, &names.0).unwrap_or_else(|| input.clone())
+}
+
+/// `source` with the markers left by [`strip`] written as `/* macro */`.
+pub fn placeholders(source: &str) -> String {
+    source.replace(MARKER, PLACEHOLDER)
+}
+
+/// The last path segment of every macro invoked in the visited item, such as
+/// `vec` for `vec![..]` and `debug` for `log::debug!(..)`.
+#[derive(Default)]
+struct MacroNames(HashSet<String>);
+
+impl<'ast> Visit<'ast> for MacroNames {
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        if let Some(segment) = node.path.segments.last() {
+            self.0.insert(segment.ident.to_string());
+        }
+        visit::visit_macro(self, node);
+    }
+}
+
+/// Replace the group after every `name!` in `input` whose name is among
+/// `names`.
+///
+/// Returns `None` when nothing is replaced. Only groups that change are
+/// rebuilt, since the compiler renders the others from their original text.
+fn replace_bodies(input: &TokenStream, names: &HashSet<String>) -> Option<TokenStream> {
+    let mut tokens: Vec<TokenTree> = input.clone().into_iter().collect();
+    let mut changed = false;
+    for i in 0..tokens.len() {
+        let TokenTree::Group(group) = &tokens[i] else {
+            continue;
+        };
+        let invoked = match (
+            i.checked_sub(2).map(|at| &tokens[at]),
+            i.checked_sub(1).map(|at| &tokens[at]),
+        ) {
+            (Some(TokenTree::Ident(name)), Some(TokenTree::Punct(bang))) => {
+                bang.as_char() == '!' && names.contains(&name.to_string())
+            }
+            _ => false,
+        };
+        let stream = if invoked {
+            TokenTree::Ident(syn::Ident::new(MARKER, Span::call_site())).into()
+        } else {
+            match replace_bodies(&group.stream(), names) {
+                Some(stream) => stream,
+                None => continue,
+            }
+        };
+        let mut inner = Group::new(group.delimiter(), stream);
+        inner.set_span(group.span());
+        tokens[i] = TokenTree::Group(inner);
+        changed = true;
+    }
+    changed.then(|| tokens.into_iter().collect())
+}
@@ -0,0 +1,75 @@
+//! `header_only`: the collapsed view of a struct or enum.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Fields, Item};
+
+use crate::pretty;
+
+/// The declaration of the struct or enum `input` without its body, such as
+/// `pub struct Foo<T> { /* 2 fields */ }`.
+///
+/// Tuple structs keep their parentheses and unit structs their `;`, and
+/// the where clause stays in place. Attributes are left out.
+pub fn header(input: &TokenStream) -> syn::Result<String> {
+    let item: Item = syn::parse2(input.clone())?;
+    match &item {
+        Item::Struct(item) => {
+            let (vis, ident, generics) = (&item.vis, &item.ident, &item.generics);
+            let where_clause = &generics.where_clause;
+            let declaration = pretty::inline(&quote!(#vis struct #ident #generics));
+            let where_clause = pretty::inline(&quote!(#where_clause));
+            Ok(match &item.fields {
+                Fields::Named(fields) => {
+                    format!(
+                        "{} {{ /* {} */ }}",
+                        join(&declaration, &where_clause),
+                        count(fields.named.len(), "field")
+                    )
+                }
+                Fields::Unnamed(fields) => {
+                    let body = format!(
+                        "{}(/* {} */)",
+                        declaration,
+                        count(fields.unnamed.len(), "field")
+                    );
+                    format!("{};", join(&body, &where_clause))
+                }
+                Fields::Unit => format!("{};", join(&declaration, &where_clause)),
+            })
+        }
+        Item::Enum(item) => {
+            let (vis, ident, generics) = (&item.vis, &item.ident, &item.generics);
+            let where_clause = &generics.where_clause;
+            let declaration = pretty::inline(&quote!(#vis enum #ident #generics));
+            let where_clause = pretty::inline(&quote!(#where_clause));
+            Ok(format!(
+                "{} {{ /* {} */ }}",
+                join(&declaration, &where_clause),
+                count(item.variants.len(), "variant")
+            ))
+        }
+        _ => Err(syn::Error::new_spanned(
+            &item,
+            "`header_only` can only be applied to a struct or an enum",
+        )),
+    }
+}
+
+/// `declaration` followed by the where clause, if there is one.
+fn join(declaration: &str, where_clause: &str) -> String {
+    if where_clause.is_empty() {
+        declaration.to_string()
+    } else {
+        format!("{} {}", declaration, where_clause)
+    }
+}
+
+/// `1 field`, `2 fields`, ...
+fn count(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", n, noun)
+    }
+}
@@ -0,0 +1,63 @@
+//! `sync_check = "path"`: a compile-time check that the embedded source
+//! equals the contents of a file, such as a snippet included in the docs.
+
+use std::path::PathBuf;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::LitStr;
+
+/// Compare `source`, the embedded source of the item shown as `display`,
+/// with the file at `path`, relative to `CARGO_MANIFEST_DIR`.
+///
+/// The comparison is made during expansion, so a mismatch is reported
+/// there, at the path, with the first line that differs. A single trailing
+/// newline in the file is ignored unless the source ends with one too, as
+/// by `assert_source_eq!`. The expansion includes the file with
+/// `include_str!`, so the check runs again whenever the file changes.
+pub fn expand(path: &LitStr, source: &str, display: &str) -> syn::Result<TokenStream> {
+    let relative = path.value();
+    let dir = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    let file = dir.join(&relative);
+    let contents = std::fs::read_to_string(&file).map_err(|err| {
+        syn::Error::new(
+            path.span(),
+            format!("cannot read `{}`: {}", file.display(), err),
+        )
+    })?;
+    let expected = match contents.strip_suffix('\n') {
+        Some(trimmed) if !source.ends_with('\n') => trimmed,
+        _ => &contents,
+    };
+    if source != expected {
+        let mut source_lines = source.split('\n');
+        let mut file_lines = expected.split('\n');
+        let (line, embedded, found) = (1..)
+            .map(|line| (line, source_lines.next(), file_lines.next()))
+            .find(|(_, embedded, found)| embedded != found)
+            .expect("different texts differ in a line");
+        return Err(syn::Error::new(
+            path.span(),
+            format!(
+                "the embedded source of `{}` differs from `{}` at line {}:\n  \
+                 embedded: {}\n  file:     {}",
+                display,
+                relative,
+                line,
+                shown(embedded),
+                shown(found)
+            ),
+        ));
+    }
+    let file = file.to_string_lossy();
+    Ok(quote! {
+        const _: &str = ::core::include_str!(#file);
+    })
+}
+
+/// A line of a comparison, or a note that the text had ended.
+fn shown(line: Option<&str>) -> String {
+    line.map_or_else(|| "(end of text)".to_string(), |line| format!("{:?}", line))
+}
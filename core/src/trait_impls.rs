@@ -0,0 +1,76 @@
+//! `src_embed_trait_impls!`: embedding a trait together with its impls.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::Item;
+
+use crate::items::{self, SourceItem};
+use crate::naming;
+
+/// The contents of a `src_embed_trait_impls!` invocation: a trait followed
+/// by impls of it.
+struct TraitImpls {
+    items: Vec<SourceItem>,
+}
+
+impl Parse for TraitImpls {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        Ok(TraitImpls {
+            items: items::parse_items(input)?,
+        })
+    }
+}
+
+/// Expand `src_embed_trait_impls! { trait ... impl ... for A {} ... }`.
+///
+/// The trait is embedded as `#[src_embed]` would embed it. Each impl is
+/// named after the trait and the implementing type, so `impl Shape for
+/// Circle` becomes `__SHAPE_FOR_CIRCLE_SOURCE__` and cannot clash with the
+/// constants of `Circle` itself. Items keep the arguments of their own
+/// `#[src_embed(...)]` attribute, and a `name` among them wins.
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let TraitImpls { items } = syn::parse2(input)?;
+    let mut items = items.into_iter();
+    let Some(first) = items.next() else {
+        return Ok(TokenStream::new());
+    };
+    let trait_item: syn::ItemTrait = syn::parse2(first.tokens.clone())
+        .map_err(|err| syn::Error::new(err.span(), "expected the trait to come first"))?;
+    let trait_options = first.options.unwrap_or_default();
+    let trait_name = naming::display_name(&Item::Trait(trait_item.clone()), &trait_options);
+
+    let (consts, item) = crate::embed(&trait_options, first.tokens, None)?;
+    let mut output = quote! {
+        #consts
+
+        #item
+    };
+    for impl_source in items {
+        let impl_item: syn::ItemImpl = syn::parse2(impl_source.tokens.clone())
+            .map_err(|err| syn::Error::new(err.span(), "expected an impl of the trait"))?;
+        let implements_trait = impl_item
+            .trait_
+            .as_ref()
+            .and_then(|(_, path, _)| path.segments.last())
+            .is_some_and(|segment| segment.ident == trait_item.ident);
+        if !implements_trait {
+            return Err(syn::Error::new_spanned(
+                &impl_item.self_ty,
+                format!("expected an impl of `{}`", trait_item.ident),
+            ));
+        }
+        let mut options = impl_source.options.unwrap_or_default();
+        if options.name.is_none() {
+            let name = naming::trait_impl_name(&trait_name, &Item::Impl(impl_item), &options);
+            options.generated_name = Some(name);
+        }
+        let (consts, item) = crate::embed(&options, impl_source.tokens, None)?;
+        output.extend(quote! {
+            #consts
+
+            #item
+        });
+    }
+    Ok(output)
+}
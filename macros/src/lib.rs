@@ -0,0 +1,328 @@
+//! Procedural macros for `src_embed`.
+//!
+//! The macros are thin wrappers around `src_embed_core`; use them through the
+//! `src_embed` crate, which re-exports them.
+
+use proc_macro::TokenStream;
+
+/// Attribute macro that embeds the original source of the annotated item.
+///
+/// When applied to an item (for example a `struct`, `enum`, `fn`, `trait`, or
+/// `impl` block) this macro produces a `pub const` string containing the
+/// textual source of that item and re-emits the original item unchanged.
+///
+/// The generated constant name is formed from the item's identifier in
+/// uppercase, wrapped between `__` and `_SOURCE__` (for example a `struct` named
+/// `Foo` will produce `__FOO_SOURCE__`). The constant is marked
+/// `#[doc(hidden)]` so it does not appear in normal documentation output,
+/// and `#[allow(dead_code)]` so that leaving it unused is not a warning.
+///
+/// # Arguments
+/// - `name = "Name"`: use `Name` instead of the derived name for every
+///   generated constant, so the source ends up in `__NAME_SOURCE__`. It
+///   always wins over the derived name, also when `full_path` is given. Since
+///   stable procedural macros cannot emit warnings, no note is shown when the
+///   item has a different name of its own.
+/// - `case = "..."`: the case of the item name in the constant names, one of
+///   `"upper"` (the default, `__HTTPSERVER_SOURCE__` for `HttpServer`),
+///   `"screaming_snake"` (`__HTTP_SERVER_SOURCE__`), `"lower"`
+///   (`__httpserver_SOURCE__`) and `"preserve"` (`__HttpServer_SOURCE__`).
+///   Only the name segment changes, and an item name that does not form a
+///   valid identifier is an error.
+/// - `feature = "name"`: only emit the constant when the named feature of the
+///   calling crate is enabled. This is sugar for gating the constant behind
+///   `#[cfg(feature = "name")]`; the annotated item itself is always emitted.
+/// - `full_path`: for `impl` blocks, build the name from every segment of the
+///   implemented type's path instead of only the last one, so
+///   `impl foo::bar::Baz` produces `__FOO_BAR_BAZ_SOURCE__` rather than
+///   `__BAZ_SOURCE__`.
+/// - `header = "checksum"`: start the embedded string with a generated
+///   `// sha: <hex>` line holding the SHA-256 of everything after that line.
+///   The constant then no longer holds pure source.
+/// - `as_static`: emit the source as a `pub static` instead of a `pub const`.
+///   A `static` has a single, stable address, which matters when the string
+///   is handed to FFI or memory-mapping code that keeps the pointer; a
+///   `const` may be instantiated at a different address at each use.
+/// - `raw`: embed the item byte for byte as written in its source file,
+///   including comments and blank lines. The `#[src_embed]` attribute itself
+///   is left out. Items that have no text of their own, such as those
+///   produced by `macro_rules!`, fall back to the token rendering.
+/// - `reindent`: together with `raw`, remove the indentation the item has in
+///   its file, so its first token starts at column zero and every other line
+///   keeps its offset from it. The column comes from the span of the first
+///   token; if it is unknown the indentation common to all lines is removed.
+///   Text containing a multi-line literal that would change is left as is.
+/// - `tabs = N`: together with `raw`, expand the tabs of each line's leading
+///   indentation to spaces, with tab stops every `N` columns (1 to 16).
+///   Tabs elsewhere in a line are kept, and so is text whose multi-line
+///   literals the expansion would change.
+/// - `pretty`: embed the item laid out one field, statement or match arm per
+///   line with four-space indentation. The layout comes from a small
+///   built-in token formatter, not from rustfmt; long lines are not wrapped
+///   and comments are not kept.
+/// - `raw, pretty`: emit both, the raw text in `__FOO_SOURCE__` and the
+///   formatted text in a second constant with the `_SOURCE_PRETTY__` suffix,
+///   `__FOO_SOURCE_PRETTY__`. The other arguments apply to both constants.
+/// - `group_by_kind`: applied to an inline `mod`, expand the module's
+///   `#[src_embed]` items and place their constants in one submodule per
+///   kind (`struct_sources`, `enum_sources`, `fn_sources`, `trait_sources`,
+///   `impl_sources`, and `item_sources` for other items). The module is the
+///   only place all these constants pass through, since separate invocations
+///   cannot add items to a shared module. The items keep their own
+///   arguments, and `group_by_kind` accepts no others.
+/// - `highlight`: also emit `__FOO_HIGHLIGHT__: &str`, a JSON object mapping
+///   the categories `types`, `traits`, `functions`, `fields`, `variants`,
+///   `values`, `lifetimes` and `macros` to the identifiers the parsed item
+///   uses in that role. Requires the `highlight` feature of `src_embed`,
+///   which is off by default: walking the syntax tree costs compile time for
+///   each annotated item.
+/// - `cow`: also emit `pub fn __foo_source_cow() -> Cow<'static, str>`
+///   returning the source constant, for consumers that want one type however
+///   the source is stored. Every capture mode currently produces a
+///   `&'static str`, returned as `Cow::Borrowed`. Requires the default `std`
+///   feature of `src_embed`.
+/// - `public_only`: for an inherent `impl` block, embed only the items
+///   declared `pub`; private and `pub(crate)` items and macro invocations are
+///   left out of the constant but not out of the impl. Trait impls are
+///   embedded whole, since all their items are public. Not allowed with
+///   `raw`.
+/// - `header_only`: for a struct or enum, embed only the declaration line
+///   (visibility, name, generics and where clause) with the number of fields
+///   or variants as a comment, such as `pub struct Foo { /* 3 fields */ }`.
+///   Tuple and unit structs keep their shape. Not allowed with `raw`,
+///   `pretty`, `golden` or `public_only`.
+/// - `build_info`: also emit `__FOO_RUST_EDITION__: &str`, the `edition` of
+///   the compiling crate's `Cargo.toml` (located through
+///   `CARGO_MANIFEST_DIR`, empty when not stated there), and
+///   `__FOO_BUILT_WITH__: &str`, the version of the compiler.
+/// - `edition = "2021"`: like `build_info`, with the given edition instead
+///   of the one from the manifest.
+/// - `cstr`: also emit `__FOO_SOURCE_CSTR__: &'static core::ffi::CStr`, the
+///   source followed by a NUL byte, for FFI. The source must not contain a
+///   NUL character itself; if it does, expansion fails with an error.
+/// - `lang = "..."`: the language label of the snippet, emitted as
+///   `__FOO_SOURCE_LANG__`. Defaults to `"rust"`.
+/// - `golden`: embed the item laid out as with `pretty`, ending in exactly
+///   one newline, so that it equals a snapshot file read with
+///   `include_str!` without trimming. Not allowed with `raw`.
+/// - `nested`: for a function, also embed every item declared directly in
+///   its body, named after the function and the item, so `fn double` in
+///   `fn outer` is embedded as `__OUTER_DOUBLE_SOURCE__`. The nested items
+///   take the other arguments of the function.
+/// - `strip_macros`: experimental and lossy. Embed every macro invocation of
+///   the item with `/* macro */` in place of its arguments, keeping the
+///   macro's path and delimiters, for comparisons that ignore macro
+///   internals. Not allowed with `raw`.
+/// - `allow_dead_code = false`: leave `#[allow(dead_code)]`, which is added by
+///   default, off the generated constants.
+/// - `visible_docs`: leave `#[doc(hidden)]` off the generated constants and
+///   give each a generated doc line instead.
+/// - `anonymize`: experimental and lossy. Embed the source with every name
+///   the item declares replaced by a generic one, such as `TypeA`, `fn_b`
+///   and `field_c`, used consistently throughout. Names only used by the
+///   item, keywords and string literals are kept. The item itself keeps its
+///   real names.
+/// - `sync_check = "path"`: fail the build unless the embedded source equals
+///   the file at `path`, relative to `CARGO_MANIFEST_DIR`, apart from one
+///   trailing newline. The file is read during expansion and included with
+///   `include_str!`, so the check reruns when it changes.
+/// - `cfg_note = "..."`: start the embedded string with a `// cfg: ...`
+///   comment line noting the build context of the snippet, such as the
+///   `cfg` of an enclosing module the macro cannot see. It is informational
+///   only and changes nothing about how the item is compiled.
+/// - `normalize_commas`: drop every trailing comma from the embedded source,
+///   that is every comma followed by a closing `)`, `]`, `}` or `>` with
+///   only whitespace and comments in between, except the comma of a
+///   one-element tuple such as `(u8,)` and, since they look the same, of
+///   any other single-element parentheses such as `fn(u8,)`. A comma before
+///   the `|` closing a closure's parameters stays too. Literals, comments and
+///   the layout are left untouched, so it combines with `raw`.
+///
+/// # Metadata constants
+/// Alongside the source constant the macro emits constants describing the
+/// item, named like the source constant with a different suffix:
+/// - `__FOO_EMBED_OPTS__: &str` for every item: the arguments of the
+///   attribute, rendered in a fixed order and spelling so that the same
+///   arguments always give the same string. It is empty without arguments.
+/// - `__FOO_SOURCE_LANG__: &str` for every item: the language of the
+///   snippet, `"rust"` unless set with `lang = "..."`.
+/// - `__FOO_ASSOC_CONSTS__: &[(&str, &str)]` for `impl` blocks: the name and
+///   initializer expression of each associated `const`, in source order.
+/// - `__FOO_IMPL_ARGS__: &[&str]` for `impl` blocks: each generic argument
+///   of the implemented type, `["u32", "String"]` for `impl Foo<u32, String>`.
+///   The slice is empty when the type takes none or is not a path.
+/// - `__FOO_QUALIFIERS__: &[&str]` for functions: the `const`, `async`,
+///   `unsafe` and `extern "ABI"` qualifiers of the signature, in source order.
+///   The slice is empty for a plain `fn`.
+/// - `__FOO_PARAMS__: &[&str]` for functions: each parameter, `self`
+///   receivers and patterns included, without its attributes.
+/// - `__FOO_RETURN__: &str` for functions: the return type, `"()"` when it is
+///   left out.
+/// - `__FOO_GENERIC_DEFAULTS__: &[(&str, &str)]` for structs, enums, unions,
+///   traits and type aliases: the name and default of each generic
+///   parameter declared with one, type and const parameters alike.
+/// - `__FOO_DEFAULTS__: &[(&str, &str)]` for traits: the name and default
+///   body, braces included, of each provided method in source order.
+///   Required methods are not listed.
+/// - `__FOO_TOKEN_SPANS__: &[(usize, usize)]` for every item, with the
+///   `token-spans` feature: the byte range of each token in
+///   `__FOO_SOURCE__`, doc comments and group delimiters counting as one
+///   token each. The ranges are read off the finished text, after every
+///   argument that rewrites it, so each one is exactly where its token is
+///   written; other comments are not tokens and have no range.
+///
+/// # Notes
+/// - If the macro cannot determine a sensible identifier (for example for
+///   certain anonymous or complex items) it falls back to `ITEM` or
+///   `UNKNOWN` in the generated constant name.
+/// - Unless `raw` is given, the source is captured from the item's tokens
+///   rather than from the file it was written in, so items generated by
+///   `macro_rules!` expansions embed their expanded tokens.
+/// - Applied through `#[cfg_attr(predicate, src_embed(...))]`, the macro
+///   behaves as if written directly whenever the predicate holds. The
+///   `cfg_attr` wrapper never appears in the embedded source.
+/// - All attributes of the item except `#[src_embed]` are embedded, above or
+///   below it. `#[cfg]`, `#[cfg_attr]` and a `#[derive]` above the attribute
+///   never reach the macro, so they are read from the source file; items
+///   without one, such as `macro_rules!` output, keep only the attributes
+///   the macro receives.
+/// - Where `#[src_embed]` sits among the other attributes does not matter:
+///   derives and other attribute macros above or below it are embedded in
+///   the order they are written, and no `#[src_embed]` attribute is.
+/// - The macro is intentionally conservative and re-emits the original item
+///   so it does not alter semantics.
+///
+/// # Example
+/// ```rust
+/// use src_embed::src_embed;
+///
+/// #[src_embed]
+/// pub fn example() -> &'static str { "hi" }
+///
+/// // The crate will also provide a constant like `__EXAMPLE_SOURCE__` with
+/// // the textual source of the function.
+///
+/// // Only embed the source when the `docs` feature is enabled.
+/// #[src_embed(feature = "docs")]
+/// pub fn documented() {}
+/// ```
+#[proc_macro_attribute]
+pub fn src_embed(args: TokenStream, input: TokenStream) -> TokenStream {
+    src_embed_core::expand(args.into(), input.into()).into()
+}
+
+/// Embed the source of every item in the block.
+///
+/// Each item is treated as if it were annotated with `#[src_embed]`. Items
+/// that do carry a `#[src_embed(...)]` attribute take their arguments from
+/// it, so one item can be renamed or captured `raw` while the rest use the
+/// defaults.
+///
+/// # Flags
+/// Flags are written as inner attributes at the top of the block:
+/// - `#![dedup]`: when an item's embedded source is identical to that of an
+///   earlier item, emit its constant as an alias of the earlier one
+///   (`pub const __BAR_SOURCE__: &str = __FOO_SOURCE__;`) so the text is
+///   stored once. Only constants of the same invocation are compared, and
+///   only those with the same `feature` gate and `const`/`static` kind.
+///
+/// # Example
+/// ```rust
+/// use src_embed::src_embed_all;
+///
+/// pub struct Unit;
+///
+/// src_embed_all! {
+///     #![dedup]
+///
+///     pub trait Marker {}
+///
+///     #[src_embed(name = "FIRST")]
+///     impl Unit {}
+///
+///     #[src_embed(name = "SECOND")]
+///     impl Unit {}
+/// }
+///
+/// assert_eq!(__MARKER_SOURCE__, "pub trait Marker {}");
+/// // `__SECOND_SOURCE__` is defined as `__FIRST_SOURCE__`.
+/// assert_eq!(__SECOND_SOURCE__, __FIRST_SOURCE__);
+/// ```
+#[proc_macro]
+pub fn src_embed_all(input: TokenStream) -> TokenStream {
+    src_embed_core::expand_all(input.into()).into()
+}
+
+/// Embed the source of a trait together with each of its impls.
+///
+/// The first item of the block must be a trait, and every following one an
+/// impl of it. The trait is embedded as `#[src_embed]` would embed it, and
+/// each impl is named after the trait and the implementing type, so
+/// `impl Shape for Circle` ends up in `__SHAPE_FOR_CIRCLE_SOURCE__`, apart
+/// from the constants of `Circle` itself. Every item is emitted unchanged,
+/// and an item annotated with `#[src_embed(...)]` takes its arguments from
+/// that attribute, a `name` among them replacing the combined one.
+///
+/// # Example
+/// ```rust
+/// use src_embed::src_embed_trait_impls;
+///
+/// pub struct Circle;
+/// pub struct Square;
+///
+/// src_embed_trait_impls! {
+///     pub trait Shape {
+///         fn sides(&self) -> u32;
+///     }
+///
+///     impl Shape for Circle {
+///         fn sides(&self) -> u32 { 0 }
+///     }
+///
+///     impl Shape for Square {
+///         fn sides(&self) -> u32 { 4 }
+///     }
+/// }
+///
+/// assert!(__SHAPE_SOURCE__.starts_with("pub trait Shape"));
+/// assert!(__SHAPE_FOR_SQUARE_SOURCE__.starts_with("impl Shape for Square"));
+/// assert_eq!(Square.sides(), 4);
+/// ```
+#[proc_macro]
+pub fn src_embed_trait_impls(input: TokenStream) -> TokenStream {
+    src_embed_core::expand_trait_impls(input.into()).into()
+}
+
+/// Assert that the embedded source of an item equals the contents of a
+/// fixture file, printing a diff of the two when it does not.
+///
+/// `assert_source_eq!(Foo, "fixtures/foo.rs")` compares `__FOO_SOURCE__`
+/// with the file `fixtures/foo.rs`. The constant is found with the default
+/// naming rules from the last segment of the path, and earlier segments
+/// are kept, so `shapes::Circle` checks `shapes::__CIRCLE_SOURCE__`. For an
+/// item embedded with `name = "..."` or `case = "..."`, write the name its
+/// constants use instead. The file is read when the assertion runs, from a
+/// path relative to the `CARGO_MANIFEST_DIR` of the crate being tested; a
+/// single newline at the end of the file is ignored when the source does
+/// not end in one.
+///
+/// Requires the `test-utils` feature of `src_embed`.
+#[cfg(feature = "test-utils")]
+#[proc_macro]
+pub fn assert_source_eq(input: TokenStream) -> TokenStream {
+    src_embed_core::expand_assert_source_eq(input.into()).into()
+}
+
+/// Assert at compile time that two items have embedded sources of the same
+/// length.
+///
+/// `assert_source_len_eq!(A, B);` expands to a `const _: () = assert!(...)`
+/// item comparing `__A_SOURCE__.len()` with `__B_SOURCE__.len()`, so a
+/// mismatch is a compile error rather than a test failure. The constants are
+/// found like those of `assert_source_eq!`: by the default naming rules from
+/// the last segment of each path, keeping the segments before it. It can be
+/// written wherever an item can, including inside a function body.
+#[proc_macro]
+pub fn assert_source_len_eq(input: TokenStream) -> TokenStream {
+    src_embed_core::expand_assert_source_len_eq(input.into()).into()
+}
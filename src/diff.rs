@@ -0,0 +1,175 @@
+//! A minimal line-based diff of two embedded sources.
+
+/// Lines of unchanged context shown around each change.
+const CONTEXT: usize = 3;
+
+/// Compare two sources line by line and describe the differences in the
+/// unified diff style.
+///
+/// The result starts with `--- a` and `+++ b` lines, followed by one `@@`
+/// hunk per group of changes with up to three lines of context around it.
+/// Removed lines start with `-`, added lines with `+` and context lines with a
+/// space. Identical inputs give an empty string.
+///
+/// Lines are compared with their line endings, so texts that differ only in
+/// those still give a diff. When one text uses CRLF endings and the other
+/// does not, each changed line ending in CRLF is followed by a
+/// `\ CRLF line ending` line; when only one of them ends in a newline, its
+/// unterminated last line is followed by `\ No newline at end of file`.
+///
+/// This is a simple longest-common-subsequence diff meant for display. It
+/// is not guaranteed to match `diff -u` or `git diff` output, nor to be
+/// accepted by `patch`. It takes time and memory proportional to the product
+/// of the line counts.
+///
+/// # Example
+/// ```rust
+/// let diff = src_embed::diff("fn a() {}\nfn b() {}", "fn a() {}\nfn c() {}");
+/// assert_eq!(diff, "--- a\n+++ b\n@@ -1,2 +1,2 @@\n fn a() {}\n-fn b() {}\n+fn c() {}\n");
+/// ```
+pub fn diff(a: &str, b: &str) -> String {
+    let old: Vec<&str> = a.split_inclusive('\n').collect();
+    let new: Vec<&str> = b.split_inclusive('\n').collect();
+    let notes = Notes {
+        crlf: a.contains("\r\n") != b.contains("\r\n"),
+        eof: !a.is_empty() && !b.is_empty() && a.ends_with('\n') != b.ends_with('\n'),
+    };
+    let edits = edits(&old, &new);
+    if edits.iter().all(|edit| matches!(edit, Edit::Keep(..))) {
+        return String::new();
+    }
+
+    let mut out = String::from("--- a\n+++ b\n");
+    let mut start = 0;
+    while let Some(first_change) = edits[start..]
+        .iter()
+        .position(|edit| !matches!(edit, Edit::Keep(..)))
+    {
+        // A hunk runs from CONTEXT lines before its first change to CONTEXT
+        // lines after a change that is followed by more than 2 * CONTEXT
+        // unchanged lines.
+        let first_change = start + first_change;
+        let hunk_start = first_change.saturating_sub(CONTEXT).max(start);
+        let mut hunk_end = first_change;
+        let mut i = first_change;
+        while i < edits.len() {
+            if !matches!(edits[i], Edit::Keep(..)) {
+                hunk_end = i + 1;
+            } else if i - hunk_end >= 2 * CONTEXT {
+                break;
+            }
+            i += 1;
+        }
+        let hunk_end = (hunk_end + CONTEXT).min(edits.len());
+        write_hunk(&mut out, &edits[hunk_start..hunk_end], notes);
+        start = hunk_end;
+    }
+    out
+}
+
+/// Which differences in line endings the diff points out.
+#[derive(Clone, Copy)]
+struct Notes {
+    /// Only one of the texts uses CRLF line endings.
+    crlf: bool,
+    /// Only one of the texts ends in a newline.
+    eof: bool,
+}
+
+/// One line of the edit script, with its 0-based line numbers in the old
+/// and new text.
+#[derive(Clone, Copy)]
+enum Edit<'a> {
+    Keep(usize, usize, &'a str),
+    Remove(usize, &'a str),
+    Add(usize, &'a str),
+}
+
+/// The edit script turning `old` into `new`, from a longest common
+/// subsequence of their lines.
+fn edits<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Edit<'a>> {
+    // lcs[i][j] is the length of the longest common subsequence of
+    // old[i..] and new[j..].
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            edits.push(Edit::Keep(i, j, old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            // Removals go first, so they are shown before their replacement.
+            edits.push(Edit::Remove(i, old[i]));
+            i += 1;
+        } else {
+            edits.push(Edit::Add(j, new[j]));
+            j += 1;
+        }
+    }
+    edits
+}
+
+fn write_hunk(out: &mut String, hunk: &[Edit], notes: Notes) {
+    let mut old_lines = Vec::new();
+    let mut new_lines = Vec::new();
+    for edit in hunk {
+        match *edit {
+            Edit::Keep(i, j, _) => {
+                old_lines.push(i);
+                new_lines.push(j);
+            }
+            Edit::Remove(i, _) => old_lines.push(i),
+            Edit::Add(j, _) => new_lines.push(j),
+        }
+    }
+    out.push_str(&format!(
+        "@@ -{} +{} @@\n",
+        range(&old_lines),
+        range(&new_lines)
+    ));
+    for edit in hunk {
+        let (marker, line) = match *edit {
+            Edit::Keep(_, _, line) => (' ', line),
+            Edit::Remove(_, line) => ('-', line),
+            Edit::Add(_, line) => ('+', line),
+        };
+        let (text, ending) = match line.strip_suffix('\n') {
+            Some(text) => match text.strip_suffix('\r') {
+                Some(text) => (text, "\r\n"),
+                None => (text, "\n"),
+            },
+            None => (line, ""),
+        };
+        out.push(marker);
+        out.push_str(text);
+        out.push('\n');
+        if notes.crlf && marker != ' ' && ending == "\r\n" {
+            out.push_str("\\ CRLF line ending\n");
+        }
+        if notes.eof && ending.is_empty() {
+            out.push_str("\\ No newline at end of file\n");
+        }
+    }
+}
+
+/// A `start,count` range with 1-based line numbers.
+///
+/// Context keeps a side of a hunk from being empty unless that whole text
+/// is, which is written `0,0`.
+fn range(lines: &[usize]) -> String {
+    match lines.first() {
+        Some(&first) => format!("{},{}", first + 1, lines.len()),
+        None => "0,0".to_string(),
+    }
+}
@@ -13,6 +13,30 @@
 //! # Supported items
 //! - `struct`, `enum`, `fn`, `trait`, and `impl` blocks
 //!
+//! [`src_embed_all!`] does the same for every item of a block, and
+//! [`src_embed_trait_impls!`] for a trait and its impls.
+//!
+//! # Build scripts
+//! Embedded constants only exist inside the compiled crate, so tooling such
+//! as a `build.rs` cannot read them from a dependency. [`scan_source`] instead
+//! parses Rust source text and reports the constants `#[src_embed]` will
+//! generate for it, using the same naming rules as the macro.
+//!
+//! # Comparing sources
+//! With the `diff` feature, `diff` returns a line-based diff of two
+//! embedded sources.
+//!
+//! # Listing every embedded item
+//! With the `manifest` feature, `manifest` returns the name, kind and
+//! source of every item embedded anywhere in the program, and `lookup`
+//! finds the source of one of them by name.
+//!
+//! # Testing sources
+//! With the `test-utils` feature, `assert_source_eq!(Foo, "fixtures/foo.rs")`
+//! checks an embedded source against a fixture file and prints a diff when
+//! they differ. [`assert_source_len_eq!`] checks at compile time that two
+//! embedded sources have the same length.
+//!
 //! # Example
 //! ```rust
 //! use src_embed::src_embed;
@@ -21,93 +45,52 @@
 //! pub struct Foo { pub x: u32 }
 //!
 //! // Expands to something like:
+//! // #[allow(dead_code)]
 //! // #[doc(hidden)]
 //! // pub const __FOO_SOURCE__: &str = "pub struct Foo { pub x: u32 }";
 //! // pub struct Foo { pub x: u32 }
 //! ```
 
-use proc_macro::TokenStream;
-use quote::quote;
+#[cfg(feature = "diff")]
+mod diff;
+mod manifest;
+mod scan;
 
-/// Attribute macro that embeds the original source of the annotated item.
-///
-/// When applied to an item (for example a `struct`, `enum`, `fn`, `trait`, or
-/// `impl` block) this macro produces a `pub const` string containing the
-/// textual source of that item and re-emits the original item unchanged.
-///
-/// The generated constant name is formed from the item's identifier in
-/// uppercase, wrapped between `__` and `_SOURCE__` (for example a `struct` named
-/// `Foo` will produce `__FOO_SOURCE__`). The constant is marked
-/// `#[doc(hidden)]` so it does not appear in normal documentation output.
-///
-/// # Notes
-/// - If the macro cannot determine a sensible identifier (for example for
-///   certain anonymous or complex items) it falls back to `ITEM` or
-///   `UNKNOWN` in the generated constant name.
-/// - The macro is intentionally conservative and re-emits the original item
-///   so it does not alter semantics.
-///
-/// # Example
-/// ```rust
-/// use src_embed::src_embed;
-///
-/// #[src_embed]
-/// pub fn example() -> &'static str { "hi" }
-///
-/// // The crate will also provide a constant like `__EXAMPLE_SOURCE__` with
-/// // the textual source of the function.
-/// ```
-#[allow(unused)]
-#[proc_macro_attribute]
-pub fn src_embed(args: TokenStream, input: TokenStream) -> TokenStream {
-    use syn::{parse_macro_input, Item, Type};
+#[cfg(feature = "diff")]
+pub use diff::diff;
+#[cfg(all(feature = "manifest", feature = "std"))]
+pub use manifest::lookup;
+#[cfg(feature = "manifest")]
+pub use manifest::{manifest, manifest_sorted, ManifestOrder};
+pub use scan::scan_source;
+#[cfg(feature = "test-utils")]
+pub use src_embed_macros::assert_source_eq;
+pub use src_embed_macros::{assert_source_len_eq, src_embed, src_embed_all, src_embed_trait_impls};
 
-    // Preserve the original token stream text (this includes attributes
-    // such as doc comments). We capture the raw input *before* parsing so
-    // that the embedded string reflects the original source as written.
-    let raw_source = input.to_string();
-
-    // Parse the input - accepts any Rust item (trait, impl, struct, etc.)
-    let input_parsed = parse_macro_input!(input as Item);
-    let source_code = syn::LitStr::new(&raw_source, proc_macro2::Span::call_site());
+/// Support code for the generated registrations. Not a public API.
+///
+/// It exists without the `manifest` feature as well, since the feature may
+/// be enabled on the macro crate alone.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::manifest::{submit, Entry};
 
-    // Extract the name of the item to generate a unique const name
-    let item_name = match &input_parsed {
-        Item::Trait(trait_item) => trait_item.ident.to_string(),
-        Item::Impl(impl_item) => {
-            // For impl blocks, extract the type being implemented for
-            if let Type::Path(type_path) = &*impl_item.self_ty {
-                type_path
-                    .path
-                    .segments
-                    .last()
-                    .map(|seg| seg.ident.to_string())
-                    .unwrap_or_else(|| "UNKNOWN".to_string())
-            } else {
-                "UNKNOWN".to_string()
-            }
+    /// The check behind `assert_source_eq!`.
+    #[cfg(feature = "test-utils")]
+    #[track_caller]
+    pub fn assert_source_eq(source: &str, fixture: &str) {
+        let expected = std::fs::read_to_string(fixture)
+            .unwrap_or_else(|err| panic!("cannot read fixture `{}`: {}", fixture, err));
+        let expected = match expected.strip_suffix('\n') {
+            Some(trimmed) if !source.ends_with('\n') => trimmed,
+            _ => &expected,
+        };
+        if source != expected {
+            panic!(
+                "embedded source does not match `{}`:\n{}",
+                fixture,
+                crate::diff(expected, source)
+            );
         }
-        Item::Struct(struct_item) => struct_item.ident.to_string(),
-        Item::Enum(enum_item) => enum_item.ident.to_string(),
-        Item::Fn(fn_item) => fn_item.sig.ident.to_string(),
-        _ => "ITEM".to_string(),
-    };
-
-    // Generate a const name: __ITEMNAME_SOURCE__
-    let const_ident = syn::Ident::new(
-        &format!("__{}_SOURCE__", item_name.to_uppercase()),
-        proc_macro2::Span::call_site(),
-    );
-
-    // Generate the output: const definition + original item. We use the
-    // captured `raw_source` as a `&'static str` literal so the embedded
-    // constant contains the original source text (including doc comments).
-    let expanded = quote! {
-        #[doc(hidden)]
-        pub const #const_ident: &str = #source_code;
-
-        #input_parsed
-    };
-
-    TokenStream::from(expanded)
-}
\ No newline at end of file
+    }
+}
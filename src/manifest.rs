@@ -0,0 +1,175 @@
+//! The collection of every item embedded in the program.
+
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+/// One registered item. Entries form a linked list starting at [`HEAD`].
+pub struct Entry {
+    pub name: &'static str,
+    pub kind: &'static str,
+    pub source: &'static str,
+    next: AtomicPtr<Entry>,
+}
+
+impl Entry {
+    pub const fn new(name: &'static str, kind: &'static str, source: &'static str) -> Self {
+        Entry {
+            name,
+            kind,
+            source,
+            next: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+}
+
+/// The most recently registered entry.
+static HEAD: AtomicPtr<Entry> = AtomicPtr::new(ptr::null_mut());
+
+/// Add `entry` to the manifest. Called once per entry, before `main`.
+pub fn submit(entry: &'static Entry) {
+    let new = entry as *const Entry as *mut Entry;
+    let mut head = HEAD.load(Ordering::Acquire);
+    loop {
+        entry.next.store(head, Ordering::Relaxed);
+        match HEAD.compare_exchange_weak(head, new, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => return,
+            Err(current) => head = current,
+        }
+    }
+}
+
+/// List every item embedded with `#[src_embed]` or `src_embed_all!` in the
+/// program, as `(name, kind, source)`.
+///
+/// The name is the one the item was given with `name = "..."` or its own
+/// name as written, for example `Foo` for `struct Foo` or the type for an
+/// `impl` block. The kind is the keyword of the item, such as `struct`,
+/// `fn` or `impl`, and the source is the value of its `__FOO_SOURCE__`
+/// constant. Items whose constants are disabled by `feature = "..."` or
+/// `cfg_attr` are not listed.
+///
+/// Requires the `manifest` feature. Since a constant cannot be assembled
+/// from several macro invocations, each embedded item registers itself as
+/// the program starts, from a function placed in the platform's list of
+/// constructors. This is supported on Linux, Android, the BSDs, illumos,
+/// macOS, iOS and Windows; elsewhere the manifest is empty.
+///
+/// The order of the entries is unspecified; [`manifest_sorted`] returns
+/// them in a fixed order.
+///
+/// # Example
+/// ```rust
+/// use src_embed::src_embed;
+///
+/// #[src_embed]
+/// pub struct Foo;
+///
+/// let (_, kind, source) = src_embed::manifest()
+///     .into_iter()
+///     .find(|(name, _, _)| name == "Foo")
+///     .unwrap();
+/// assert_eq!(kind, "struct");
+/// assert_eq!(source, __FOO_SOURCE__);
+/// ```
+#[cfg(feature = "manifest")]
+pub fn manifest() -> Vec<(String, String, String)> {
+    let mut entries = Vec::new();
+    let mut current = HEAD.load(Ordering::Acquire);
+    // SAFETY: every pointer in the list comes from a `&'static Entry`.
+    while let Some(entry) = unsafe { current.as_ref() } {
+        entries.push((
+            entry.name.to_string(),
+            entry.kind.to_string(),
+            entry.source.to_string(),
+        ));
+        current = entry.next.load(Ordering::Acquire);
+    }
+    entries
+}
+
+/// How [`manifest_sorted`] orders the entries of the manifest.
+#[cfg(feature = "manifest")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ManifestOrder {
+    /// By name, then kind.
+    Name,
+    /// By kind, then name, grouping all structs, all functions and so on.
+    KindThenName,
+}
+
+/// [`manifest`] in a fixed order, for output that has to be the same on
+/// every build, such as generated documentation.
+///
+/// Entries that share their name and kind are ordered by their source, so
+/// the result only depends on what was embedded, not on the order the
+/// linker placed the items in. The order of [`manifest`] itself stays
+/// unspecified.
+///
+/// # Example
+/// ```rust
+/// use src_embed::{src_embed, ManifestOrder};
+///
+/// #[src_embed]
+/// pub struct Beta;
+///
+/// #[src_embed]
+/// pub fn alpha() {}
+///
+/// let names: Vec<String> = src_embed::manifest_sorted(ManifestOrder::Name)
+///     .into_iter()
+///     .map(|(name, _, _)| name)
+///     .collect();
+/// assert_eq!(names, ["Beta", "alpha"]);
+/// ```
+#[cfg(feature = "manifest")]
+pub fn manifest_sorted(order: ManifestOrder) -> Vec<(String, String, String)> {
+    let mut entries = manifest();
+    match order {
+        ManifestOrder::Name => entries.sort(),
+        ManifestOrder::KindThenName => {
+            entries.sort_by(|(a_name, a_kind, a_source), (b_name, b_kind, b_source)| {
+                (a_kind, a_name, a_source).cmp(&(b_kind, b_name, b_source))
+            })
+        }
+    }
+    entries
+}
+/// Find the source of the embedded item called `name`.
+///
+/// Names are those listed by [`manifest`]: the item's own name as written,
+/// such as `"Foo"` for `struct Foo`, or its `name = "..."`. The entries are
+/// collected into a map on the first call, so later lookups do not walk the
+/// whole manifest. When several items share a name, for example a struct
+/// and its `impl` block, which one is returned is unspecified; use
+/// [`manifest`] to tell them apart by kind.
+///
+/// Requires the `manifest` and `std` features.
+///
+/// # Example
+/// ```rust
+/// use src_embed::src_embed;
+///
+/// #[src_embed]
+/// pub fn greet() -> &'static str { "hello" }
+///
+/// assert_eq!(src_embed::lookup("greet"), Some(__GREET_SOURCE__));
+/// assert_eq!(src_embed::lookup("missing"), None);
+/// ```
+#[cfg(all(feature = "manifest", feature = "std"))]
+pub fn lookup(name: &str) -> Option<&'static str> {
+    use std::collections::HashMap;
+    use std::sync::OnceLock;
+
+    static SOURCES: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    let sources = SOURCES.get_or_init(|| {
+        let mut sources = HashMap::new();
+        let mut current = HEAD.load(Ordering::Acquire);
+        // SAFETY: every pointer in the list comes from a `&'static Entry`.
+        while let Some(entry) = unsafe { current.as_ref() } {
+            sources.entry(entry.name).or_insert(entry.source);
+            current = entry.next.load(Ordering::Acquire);
+        }
+        sources
+    });
+    sources.get(name).copied()
+}
@@ -0,0 +1,148 @@
+//! Standalone scanning of Rust source text for `#[src_embed]` items.
+
+use quote::ToTokens;
+use src_embed_core::{attrs, naming, Options};
+use syn::{Item, Stmt};
+
+/// Find every `#[src_embed]` item in a Rust source file.
+///
+/// `text` is parsed as a complete Rust file, including items nested in inline
+/// modules. For each item annotated with `#[src_embed]` (or
+/// `#[src_embed::src_embed]`) or written inside `src_embed_all! { ... }` or
+/// `src_embed_trait_impls! { ... }`, and for the items `nested` embeds from
+/// the body of a function, this returns the name of the constant the macro
+/// will generate together with the item's source, rendered from its tokens
+/// without the `#[src_embed]` attribute. The arguments of the attribute are
+/// honoured, so for example `full_path` changes the predicted name exactly as
+/// it does for the macro.
+///
+/// The rendered source has the same tokens as the generated constant, but
+/// its whitespace may differ because the compiler formats the constant.
+///
+/// Text that does not parse as a Rust file yields an empty list, and items
+/// whose attribute arguments are invalid are skipped, since the macro would
+/// reject them as well.
+///
+/// # Example
+/// ```rust
+/// let found = src_embed::scan_source("#[src_embed] pub struct Foo;");
+/// assert_eq!(found[0].0, "__FOO_SOURCE__");
+/// ```
+pub fn scan_source(text: &str) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+    if let Ok(file) = syn::parse_file(text) {
+        scan_items(&file.items, &mut found);
+    }
+    found
+}
+
+fn scan_items(items: &[Item], found: &mut Vec<(String, String)>) {
+    for item in items {
+        let mut item = item.clone();
+        if let Some(attr) = attrs::take_src_embed(&mut item) {
+            if let Ok(options) = Options::from_attribute(&attr) {
+                scan_item(item.clone(), &options, found);
+            }
+        } else if let Some(items) = macro_items(&item, "src_embed_all") {
+            for mut item in items {
+                if let Ok(options) = item_options(&mut item) {
+                    scan_item(item, &options, found);
+                }
+            }
+        } else if let Some(items) = macro_items(&item, "src_embed_trait_impls") {
+            scan_trait_impls(items, found);
+        }
+        if let Item::Mod(module) = &item {
+            if let Some((_, items)) = &module.content {
+                scan_items(items, found);
+            }
+        }
+    }
+}
+
+fn scan_item(item: Item, options: &Options, found: &mut Vec<(String, String)>) {
+    // A grouping module embeds its items, which are scanned with the rest
+    // of the module, not itself.
+    if options.group_by_kind {
+        return;
+    }
+    let name = naming::source_const_name(&naming::item_name(&item, options));
+    found.push((name, item.to_token_stream().to_string()));
+    if let (Item::Fn(function), true) = (&item, options.nested && !options.nested_item) {
+        let parent = naming::display_name(&item, options);
+        for stmt in &function.block.stmts {
+            if let Stmt::Item(nested) = stmt {
+                let nested_options = Options {
+                    generated_name: Some(naming::nested_name(&parent, nested, options)),
+                    nested_item: true,
+                    ..options.clone()
+                };
+                scan_item(nested.clone(), &nested_options, found);
+            }
+        }
+    }
+}
+
+/// The trait and impls of a `src_embed_trait_impls! { ... }` invocation,
+/// named as the macro names them. Invocations the macro would reject are
+/// scanned only up to the first item it rejects.
+fn scan_trait_impls(items: Vec<Item>, found: &mut Vec<(String, String)>) {
+    let mut items = items.into_iter();
+    let Some(mut trait_item) = items.next() else {
+        return;
+    };
+    let Ok(trait_options) = item_options(&mut trait_item) else {
+        return;
+    };
+    let Item::Trait(trait_decl) = &trait_item else {
+        return;
+    };
+    let trait_ident = trait_decl.ident.clone();
+    let trait_name = naming::display_name(&trait_item, &trait_options);
+    scan_item(trait_item, &trait_options, found);
+    for mut impl_item in items {
+        let Ok(mut options) = item_options(&mut impl_item) else {
+            return;
+        };
+        let implements_trait = match &impl_item {
+            Item::Impl(impl_decl) => impl_decl
+                .trait_
+                .as_ref()
+                .and_then(|(_, path, _)| path.segments.last())
+                .is_some_and(|segment| segment.ident == trait_ident),
+            _ => false,
+        };
+        if !implements_trait {
+            return;
+        }
+        if options.name.is_none() {
+            options.generated_name =
+                Some(naming::trait_impl_name(&trait_name, &impl_item, &options));
+        }
+        scan_item(impl_item, &options, found);
+    }
+}
+
+/// The arguments of the `#[src_embed(...)]` attribute of an item in a
+/// block macro, removing the attribute; the defaults without one.
+fn item_options(item: &mut Item) -> syn::Result<Options> {
+    match attrs::take_src_embed(item) {
+        Some(attr) => Options::from_attribute(&attr),
+        None => Ok(Options::default()),
+    }
+}
+
+/// The items of an invocation of the block macro `name`, such as
+/// `src_embed_all! { ... }`.
+fn macro_items(item: &Item, name: &str) -> Option<Vec<Item>> {
+    let Item::Macro(invocation) = item else {
+        return None;
+    };
+    let segment = invocation.mac.path.segments.last()?;
+    if segment.ident != name {
+        return None;
+    }
+    syn::parse2::<syn::File>(invocation.mac.tokens.clone())
+        .ok()
+        .map(|block| block.items)
+}
@@ -0,0 +1,161 @@
+//! `anonymize` replaces the names an item declares with generic ones in the
+//! embedded source.
+
+use src_embed::src_embed;
+
+#[src_embed(anonymize, pretty)]
+pub struct Invoice {
+    pub customer: String,
+    pub total: u64,
+}
+
+#[src_embed(name = "INVOICE_IMPL", anonymize, pretty)]
+impl Invoice {
+    pub fn with_tax(&self, rate: u64) -> u64 {
+        let tax = self.total * rate / 100;
+        self.total + tax
+    }
+}
+
+#[src_embed(anonymize, raw)]
+pub enum Status<'a, T> {
+    // A `Paid` invoice has a `receipt`.
+    Paid { receipt: &'a T },
+    Open,
+}
+
+#[src_embed(name = "INVOICE_DISPLAY", anonymize, pretty)]
+impl std::fmt::Display for Invoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invoice for {}", self.customer)
+    }
+}
+
+#[test]
+fn declared_names_are_replaced() {
+    assert_eq!(
+        __INVOICE_SOURCE__,
+        "pub struct TypeA {\n    pub field_b: String,\n    pub field_c: u64,\n}"
+    );
+    let invoice = Invoice {
+        customer: "Ada".into(),
+        total: 200,
+    };
+    assert_eq!(invoice.with_tax(10), 220);
+}
+
+#[test]
+fn names_are_used_consistently() {
+    assert_eq!(
+        __INVOICE_IMPL_SOURCE__,
+        "impl TypeA {\n    pub fn fn_b(&self, var_c: u64) -> u64 {\n        \
+         let var_d = self.total * var_c / 100;\n        self.total + var_d\n    }\n}"
+    );
+}
+
+#[test]
+fn raw_captures_keep_their_layout() {
+    // Generic parameters and lifetimes keep their names; comments are renamed.
+    assert_eq!(
+        __STATUS_SOURCE__,
+        "pub enum TypeA<'a, T> {\n    // A `VariantB` invoice has a `field_c`.\n    \
+         VariantB { field_c: &'a T },\n    VariantD,\n}"
+    );
+    let receipt = 7;
+    assert!(matches!(
+        Status::Paid { receipt: &receipt },
+        Status::Paid { .. }
+    ));
+    assert!(matches!(Status::<u8>::Open, Status::Open));
+}
+
+#[test]
+fn trait_impls_keep_the_trait_names() {
+    assert_eq!(
+        __INVOICE_DISPLAY_SOURCE__
+            .split_whitespace()
+            .collect::<String>(),
+        "implstd::fmt::DisplayforInvoice{\
+         fnfmt(&self,var_a:&mutstd::fmt::Formatter<'_>)->std::fmt::Result{\
+         write!(var_a,\"Invoicefor{}\",self.customer)}}"
+    );
+    assert_eq!(
+        Invoice {
+            customer: "Ada".into(),
+            total: 0
+        }
+        .to_string(),
+        "Invoice for Ada"
+    );
+}
+
+#[src_embed(anonymize, raw)]
+pub struct Stack {
+    items: Vec<u32>,
+}
+
+#[src_embed(name = "STACK_IMPL", anonymize, raw)]
+impl Stack {
+    fn new() -> Self {
+        Stack { items: Vec::new() }
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn map(&self, f: fn(u32) -> u32) -> Vec<u32> {
+        self.items.iter().copied().map(f).collect()
+    }
+
+    fn twice(&self) -> Self {
+        let doubled = self.map(|item| item * 2);
+        Self { items: doubled }
+    }
+}
+
+#[test]
+fn names_of_other_types_are_kept() {
+    // `new`, `len` and `map` are declared, but `Vec::new()`, `.len()` and
+    // `.map(f)` are std's own.
+    assert_eq!(
+        __STACK_IMPL_SOURCE__,
+        "impl TypeA {\n    fn fn_b() -> Self {\n        TypeA { items: Vec::new() }\n    \
+         }\n\n    fn fn_c(&self) -> usize {\n        self.items.len()\n    }\n\n    \
+         fn fn_d(&self, var_e: fn(u32) -> u32) -> Vec<u32> {\n        \
+         self.items.iter().copied().map(var_e).collect()\n    }\n\n    \
+         fn fn_f(&self) -> Self {\n        let var_g = self.fn_d(|var_h| var_h * 2);\n        \
+         Self { items: var_g }\n    }\n}"
+    );
+    assert_eq!(Stack { items: vec![1, 2] }.twice().len(), 2);
+    assert_eq!(Stack::new().len(), 0);
+}
+
+#[src_embed(name = "TALLY_IMPL", anonymize, raw)]
+impl Tally {
+    fn total(&self) -> u32 {
+        self.count
+    }
+
+    fn add(&self, other: &Tally) -> u32 {
+        self.total() + other.total() + other.count
+    }
+}
+
+pub struct Tally {
+    count: u32,
+}
+
+#[test]
+fn declared_methods_are_renamed_on_other_values() {
+    // `total` is the item's own method wherever it is called, while `count`
+    // is a field declared elsewhere.
+    assert_eq!(
+        __TALLY_IMPL_SOURCE__,
+        "impl TypeA {\n    fn fn_b(&self) -> u32 {\n        self.count\n    }\n\n    \
+         fn fn_c(&self, var_d: &TypeA) -> u32 {\n        \
+         self.fn_b() + var_d.fn_b() + var_d.count\n    }\n}"
+    );
+    let tally = Tally { count: 2 };
+    assert_eq!(tally.add(&tally), 6);
+}
@@ -0,0 +1,31 @@
+//! `as_static` emits the source as a `static` with the same value.
+
+pub mod as_static {
+    use src_embed::src_embed;
+
+    #[src_embed(as_static)]
+    pub struct Foo {
+        pub x: u32,
+    }
+}
+
+pub mod as_const {
+    use src_embed::src_embed;
+
+    #[src_embed]
+    pub struct Foo {
+        pub x: u32,
+    }
+}
+
+#[test]
+fn static_matches_const() {
+    assert_eq!(as_static::__FOO_SOURCE__, as_const::__FOO_SOURCE__);
+}
+
+#[test]
+fn static_has_a_stable_address() {
+    let first: &'static &'static str = &as_static::__FOO_SOURCE__;
+    let second: &'static &'static str = &as_static::__FOO_SOURCE__;
+    assert!(std::ptr::eq(first, second));
+}
@@ -0,0 +1,31 @@
+//! `assert_source_eq!` compares an embedded source with a fixture file.
+#![cfg(feature = "test-utils")]
+
+use src_embed::assert_source_eq;
+
+pub mod shapes {
+    use src_embed::src_embed;
+
+    #[src_embed]
+    pub struct Shape {
+        pub sides: u8,
+    }
+}
+
+#[test]
+fn matching_fixture_passes() {
+    let _ = shapes::Shape { sides: 3 };
+    assert_source_eq!(shapes::Shape, "tests/fixtures/shape.rs");
+}
+
+#[test]
+#[should_panic(expected = "embedded source does not match")]
+fn mismatch_panics_with_a_diff() {
+    assert_source_eq!(shapes::Shape, "tests/assert_source_eq.rs");
+}
+
+#[test]
+#[should_panic(expected = "cannot read fixture")]
+fn missing_fixture_panics() {
+    assert_source_eq!(shapes::Shape, "tests/fixtures/missing.rs");
+}
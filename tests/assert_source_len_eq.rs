@@ -0,0 +1,39 @@
+//! `assert_source_len_eq!` compares the lengths of two sources at compile
+//! time.
+
+use src_embed::{assert_source_len_eq, src_embed};
+
+mod unix {
+    use src_embed::src_embed;
+
+    #[src_embed]
+    pub fn probe() -> u8 {
+        1
+    }
+}
+
+mod win {
+    use src_embed::src_embed;
+
+    #[src_embed]
+    pub fn probe() -> u8 {
+        2
+    }
+}
+
+#[src_embed]
+pub struct Alpha;
+
+#[src_embed]
+pub struct Omega;
+
+assert_source_len_eq!(unix::probe, win::probe);
+assert_source_len_eq!(Alpha, Omega,);
+
+#[test]
+fn equal_lengths_compile() {
+    assert_source_len_eq!(crate::Alpha, self::Omega);
+    assert_eq!(unix::probe() + win::probe(), 3);
+    let _ = (Alpha, Omega);
+    assert_eq!(unix::__PROBE_SOURCE__.len(), win::__PROBE_SOURCE__.len());
+}
@@ -0,0 +1,75 @@
+//! Every attribute of the item except `#[src_embed]` is embedded.
+
+use src_embed::src_embed;
+
+#[cfg(not(miri))]
+/// Doubles a value.
+#[inline]
+#[src_embed]
+#[cfg(not(doc))]
+#[must_use]
+pub fn double(value: u32) -> u32 {
+    value * 2
+}
+
+#[derive(Debug, Clone)]
+#[src_embed(raw)]
+#[cfg_attr(all(), derive(PartialEq))]
+#[allow(dead_code)]
+pub struct Point {
+    pub x: i32,
+}
+
+#[src_embed(pretty)]
+#[cfg(not(miri))]
+pub enum Axis {
+    X,
+}
+
+pub struct Before; // #[inline]
+#[src_embed]
+pub struct After;
+
+pub struct BeforeRaw; // "#[cold]"
+#[src_embed(raw)]
+pub struct AfterRaw;
+
+#[test]
+fn captures_attributes_above_and_below() {
+    assert_eq!(double(2), 4);
+    assert_eq!(
+        __DOUBLE_SOURCE__,
+        "#[cfg(not(miri))] /// Doubles a value.\n#[inline] #[cfg(not(doc))] #[must_use] \
+         pub fn double(value: u32) -> u32 { value * 2 }"
+    );
+}
+
+#[test]
+fn raw_keeps_attributes_as_written() {
+    assert_eq!(Point { x: 1 }.clone(), Point { x: 1 });
+    assert_eq!(
+        __POINT_SOURCE__,
+        "#[derive(Debug, Clone)]
+#[cfg_attr(all(), derive(PartialEq))]
+#[allow(dead_code)]
+pub struct Point {
+    pub x: i32,
+}"
+    );
+}
+
+#[test]
+fn pretty_includes_removed_cfg() {
+    let _ = Axis::X;
+    assert_eq!(
+        __AXIS_SOURCE__,
+        "#[cfg(not(miri))]\npub enum Axis {\n    X,\n}"
+    );
+}
+
+#[test]
+fn attributes_in_comments_of_a_previous_line_are_not_captured() {
+    let _ = (Before, After, BeforeRaw, AfterRaw);
+    assert_eq!(__AFTER_SOURCE__, "pub struct After;");
+    assert_eq!(__AFTERRAW_SOURCE__, "pub struct AfterRaw;");
+}
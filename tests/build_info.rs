@@ -0,0 +1,32 @@
+//! `build_info` records the edition and compiler an item was embedded with.
+
+use src_embed::src_embed;
+
+#[src_embed(build_info)]
+pub struct Audited;
+
+#[src_embed(edition = "2024")]
+pub struct Pinned;
+
+#[test]
+fn edition_comes_from_the_manifest() {
+    let _ = Audited;
+    assert_eq!(__AUDITED_RUST_EDITION__, "2021");
+}
+
+#[test]
+fn edition_argument_takes_precedence() {
+    let _ = Pinned;
+    assert_eq!(__PINNED_RUST_EDITION__, "2024");
+    assert_eq!(__PINNED_EMBED_OPTS__, "edition = \"2024\"");
+}
+
+#[test]
+fn compiler_version_is_recorded() {
+    assert!(
+        __AUDITED_BUILT_WITH__.starts_with("rustc "),
+        "{}",
+        __AUDITED_BUILT_WITH__
+    );
+    assert_eq!(__AUDITED_BUILT_WITH__, __PINNED_BUILT_WITH__);
+}
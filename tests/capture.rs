@@ -0,0 +1,216 @@
+//! `raw` and `pretty` capture the item as written and as laid out by the
+//! built-in formatter, and both can be requested at once.
+
+use src_embed::src_embed;
+
+/// Scales a value.
+#[src_embed(raw, pretty)]
+pub fn scale(value: u32, factor: u32) -> u32 {
+    // Multiply first.
+    let product = value * factor; /* then clamp */
+    match product {
+        0 => 1,
+        n => n,
+    }
+}
+
+// Written unformatted on purpose.
+#[rustfmt::skip]
+pub mod written {
+    use src_embed::src_embed;
+
+    #[src_embed(raw)]
+    pub struct Spaced   {
+        pub a: u8,
+
+        // Kept as written.
+        pub b: u8,
+    }
+
+    #[src_embed(pretty)]
+    pub struct Config { pub name: String, pub retries: u32, pub verbose: bool }
+
+    pub mod config_impl {
+        use super::{src_embed, Config};
+
+        #[src_embed(pretty)]
+        impl Config { pub fn quiet(&self) -> bool { !self.verbose && self.retries < 5 } }
+    }
+
+    pub const LIMIT: usize = 8;
+
+    #[src_embed(pretty)]
+    pub fn fits<const N: usize, const M: usize>() -> bool { if N < M { true } else { N == M || LIMIT < N && M > 1 } }
+
+    #[src_embed(pretty)]
+    pub fn closures(values: &[u8]) -> Vec<i32> { let big = values.iter().filter(|x| *x > &3).count(); let flags = values.iter().map(|a: &u8| !*a).collect::<Vec<_>>(); let refs = values.iter().any(|x| &3 == x) as usize; values.iter().map(|x| -(*x as i32) + (big + flags.len() + refs) as i32).collect() }
+}
+
+use written::{closures, fits, Config};
+
+macro_rules! getter {
+    ($name:ident, $value:expr) => {
+        #[src_embed(raw)]
+        pub fn $name() -> u32 {
+            $value
+        }
+    };
+}
+
+getter!(answer, 40 + 2);
+
+#[test]
+fn raw_keeps_comments_and_layout() {
+    assert_eq!(scale(2, 3), 6);
+    assert_eq!(
+        __SCALE_SOURCE__,
+        "/// Scales a value.
+pub fn scale(value: u32, factor: u32) -> u32 {
+    // Multiply first.
+    let product = value * factor; /* then clamp */
+    match product {
+        0 => 1,
+        n => n,
+    }
+}"
+    );
+    assert_eq!(
+        written::__SPACED_SOURCE__,
+        "pub struct Spaced   {
+        pub a: u8,
+
+        // Kept as written.
+        pub b: u8,
+    }"
+    );
+}
+
+#[test]
+fn raw_and_pretty_emit_both_consts() {
+    assert_eq!(
+        __SCALE_SOURCE_PRETTY__,
+        "/// Scales a value.
+pub fn scale(value: u32, factor: u32) -> u32 {
+    let product = value * factor;
+    match product {
+        0 => 1,
+        n => n,
+    }
+}"
+    );
+}
+
+#[test]
+fn pretty_alone_replaces_the_source() {
+    let config = Config {
+        name: String::new(),
+        retries: 0,
+        verbose: false,
+    };
+    assert!(config.quiet() && config.name.is_empty());
+    assert_eq!(
+        written::__CONFIG_SOURCE__,
+        "pub struct Config {
+    pub name: String,
+    pub retries: u32,
+    pub verbose: bool,
+}"
+    );
+    assert_eq!(
+        written::config_impl::__CONFIG_SOURCE__,
+        "impl Config {
+    pub fn quiet(&self) -> bool {
+        !self.verbose && self.retries < 5
+    }
+}"
+    );
+}
+
+#[test]
+fn raw_falls_back_to_tokens_inside_macro_expansions() {
+    assert_eq!(answer(), 42);
+    assert!(__ANSWER_SOURCE__.starts_with("pub fn answer"));
+    assert!(__ANSWER_SOURCE__.contains("40 + 2"));
+}
+
+pub mod indented {
+    pub mod deeper {
+        use src_embed::src_embed;
+
+        #[src_embed(raw, reindent)]
+        pub fn nested(flag: bool) -> u32 {
+            if flag {
+                1
+            } else {
+                0
+            }
+        }
+
+        #[src_embed(raw)]
+        pub struct KeepsIndent {
+            pub a: u8,
+        }
+
+        #[src_embed(raw, reindent)]
+        pub fn with_literal() -> &'static str {
+            "first
+            second"
+        }
+    }
+}
+
+#[test]
+fn reindent_moves_the_item_to_column_zero() {
+    assert_eq!(indented::deeper::nested(true), 1);
+    assert_eq!(
+        indented::deeper::__NESTED_SOURCE__,
+        "pub fn nested(flag: bool) -> u32 {
+    if flag {
+        1
+    } else {
+        0
+    }
+}"
+    );
+    assert_eq!(
+        indented::deeper::__KEEPSINDENT_SOURCE__,
+        "pub struct KeepsIndent {
+            pub a: u8,
+        }"
+    );
+}
+
+#[test]
+fn reindent_leaves_multi_line_literals_alone() {
+    assert!(indented::deeper::with_literal().contains("\n            second"));
+    assert!(indented::deeper::__WITH_LITERAL_SOURCE__.ends_with("\n        }"));
+}
+
+#[test]
+fn pretty_closure_bodies_start_with_unary_operators() {
+    assert_eq!(closures(&[4]), [-2]);
+    assert_eq!(
+        written::__CLOSURES_SOURCE__,
+        "pub fn closures(values: &[u8]) -> Vec<i32> {
+    let big = values.iter().filter(|x| *x > &3).count();
+    let flags = values.iter().map(|a: &u8| !*a).collect::<Vec<_>>();
+    let refs = values.iter().any(|x| &3 == x) as usize;
+    values.iter().map(|x| -(*x as i32) + (big + flags.len() + refs) as i32).collect()
+}"
+    );
+}
+
+#[test]
+fn pretty_comparisons_of_constants_keep_their_spaces() {
+    assert!(fits::<1, 2>() && !fits::<2, 1>());
+    assert_eq!(
+        written::__FITS_SOURCE__,
+        "pub fn fits<const N: usize, const M: usize>() -> bool {
+    if N < M {
+        true
+    } else {
+        N == M || LIMIT < N && M > 1
+    }
+}"
+    );
+}
@@ -0,0 +1,67 @@
+//! `#[cfg_attr(predicate, src_embed)]` behaves like `#[src_embed]` when the
+//! predicate holds and does nothing otherwise.
+
+pub mod active {
+    use src_embed::src_embed;
+
+    #[cfg_attr(all(), src_embed)]
+    pub struct Foo {
+        pub x: u32,
+    }
+
+    /// Documented.
+    #[cfg_attr(all(), src_embed(raw))]
+    #[derive(Clone)]
+    pub struct Raw;
+
+    #[derive(Debug)]
+    #[cfg_attr(not(any()), src_embed(name = "RENAMED"))]
+    pub enum Kind {
+        A,
+    }
+}
+
+pub mod direct {
+    use src_embed::src_embed;
+
+    #[src_embed]
+    pub struct Foo {
+        pub x: u32,
+    }
+
+    #[derive(Debug)]
+    #[src_embed(name = "RENAMED")]
+    pub enum Kind {
+        A,
+    }
+}
+
+pub mod inactive {
+    #[cfg_attr(any(), src_embed::src_embed)]
+    pub struct Foo;
+
+    // Would clash with the constant if the attribute had been applied.
+    pub const __FOO_SOURCE__: &str = "not embedded";
+}
+
+#[test]
+fn active_cfg_attr_matches_direct_attribute() {
+    assert_eq!(active::__FOO_SOURCE__, direct::__FOO_SOURCE__);
+    assert_eq!(active::__RENAMED_SOURCE__, direct::__RENAMED_SOURCE__);
+    assert!(!active::__FOO_SOURCE__.contains("cfg_attr"));
+}
+
+#[test]
+fn raw_capture_leaves_out_the_wrapper() {
+    let _ = active::Raw.clone();
+    assert_eq!(
+        active::__RAW_SOURCE__,
+        "/// Documented.\n    #[derive(Clone)]\n    pub struct Raw;"
+    );
+}
+
+#[test]
+fn inactive_cfg_attr_emits_nothing() {
+    let _ = inactive::Foo;
+    assert_eq!(inactive::__FOO_SOURCE__, "not embedded");
+}
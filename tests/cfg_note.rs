@@ -0,0 +1,39 @@
+//! `cfg_note = "..."` records the build context above the embedded source.
+
+use src_embed::src_embed;
+
+#[cfg(unix)]
+mod platform {
+    use src_embed::src_embed;
+
+    #[src_embed(cfg_note = "unix")]
+    pub fn separator() -> char {
+        '/'
+    }
+}
+
+#[src_embed(cfg_note = "any(unix, windows)", header = "checksum")]
+pub struct Portable;
+
+#[cfg(unix)]
+#[test]
+fn note_is_the_first_line() {
+    assert_eq!(platform::separator(), '/');
+    assert_eq!(
+        platform::__SEPARATOR_SOURCE__,
+        "// cfg: unix\npub fn separator() -> char { '/' }"
+    );
+    assert_eq!(platform::__SEPARATOR_EMBED_OPTS__, "cfg_note = \"unix\"");
+}
+
+#[test]
+fn checksum_covers_the_note() {
+    let _ = Portable;
+    let (checksum, rest) = __PORTABLE_SOURCE__.split_once('\n').expect("checksum line");
+    assert!(checksum.starts_with("// sha: "));
+    assert_eq!(rest, "// cfg: any(unix, windows)\npub struct Portable;");
+    assert_eq!(
+        __PORTABLE_EMBED_OPTS__,
+        "cfg_note = \"any(unix, windows)\", header = \"checksum\""
+    );
+}
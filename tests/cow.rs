@@ -0,0 +1,35 @@
+//! `cow` emits an accessor returning the source as a `Cow<'static, str>`.
+
+use std::borrow::Cow;
+
+use src_embed::src_embed;
+
+#[src_embed(cow)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[src_embed(cow, as_static)]
+pub fn origin() -> Point {
+    Point { x: 0, y: 0 }
+}
+
+fn describe(source: Cow<'static, str>) -> usize {
+    source.lines().count()
+}
+
+#[test]
+fn accessor_borrows_the_source_const() {
+    let _ = origin().x + origin().y;
+    assert!(matches!(__point_source_cow(), Cow::Borrowed(s) if s == __POINT_SOURCE__));
+    assert_eq!(__origin_source_cow(), __ORIGIN_SOURCE__);
+}
+
+#[test]
+fn accessor_fits_generic_consumers() {
+    assert_eq!(
+        describe(__point_source_cow()),
+        __POINT_SOURCE__.lines().count()
+    );
+}
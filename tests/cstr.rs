@@ -0,0 +1,52 @@
+//! `cstr` also emits the source as a NUL-terminated C string.
+
+use std::ffi::CStr;
+
+use src_embed::src_embed;
+
+#[src_embed(cstr)]
+pub fn greet() -> &'static str {
+    "héllo\tworld"
+}
+
+#[src_embed(cstr, header = "checksum")]
+pub struct Checked;
+
+#[test]
+fn cstr_matches_the_source() {
+    assert_eq!(greet(), "héllo\tworld");
+    let source: &CStr = __GREET_SOURCE_CSTR__;
+    assert_eq!(source.to_str(), Ok(__GREET_SOURCE__));
+    assert_eq!(source.to_bytes_with_nul().last(), Some(&0));
+}
+
+#[test]
+fn cstr_includes_the_header() {
+    let _ = Checked;
+    assert_eq!(__CHECKED_SOURCE_CSTR__.to_str(), Ok(__CHECKED_SOURCE__));
+    assert!(__CHECKED_SOURCE__.starts_with("// sha: "));
+}
+
+#[test]
+fn interior_nul_is_an_error() {
+    // The item's string literal holds a NUL character itself, not the
+    // `\0` escape, which the source only spells out.
+    let expanded = src_embed_core::expand(
+        "cstr".parse().unwrap(),
+        "pub const NUL: &str = \"a\0b\";".parse().unwrap(),
+    )
+    .to_string();
+    assert!(expanded.contains("compile_error"), "{}", expanded);
+    assert!(
+        expanded.contains("`cstr` needs a source without NUL characters"),
+        "{}",
+        expanded
+    );
+
+    let escaped = src_embed_core::expand(
+        "cstr".parse().unwrap(),
+        "pub const NUL: &str = \"a\\0b\";".parse().unwrap(),
+    )
+    .to_string();
+    assert!(!escaped.contains("compile_error"), "{}", escaped);
+}
@@ -0,0 +1,70 @@
+//! `diff` describes the line changes between two sources.
+#![cfg(feature = "diff")]
+
+use src_embed::diff;
+
+#[test]
+fn identical_sources_have_no_diff() {
+    assert_eq!(diff("fn a() {}\n", "fn a() {}\n"), "");
+    assert_eq!(diff("", ""), "");
+}
+
+#[test]
+fn changed_line_is_removed_and_added() {
+    let old = "struct A {\n    x: u8,\n}";
+    let new = "struct A {\n    x: u16,\n}";
+    assert_eq!(
+        diff(old, new),
+        "--- a\n+++ b\n@@ -1,3 +1,3 @@\n struct A {\n-    x: u8,\n+    x: u16,\n }\n"
+    );
+}
+
+#[test]
+fn distant_changes_get_separate_hunks() {
+    let old: Vec<String> = (1..=20).map(|n| format!("line {n}")).collect();
+    let mut new = old.clone();
+    new[1] = "changed 2".to_string();
+    new.insert(18, "inserted".to_string());
+    let diff = diff(&old.join("\n"), &new.join("\n"));
+    assert_eq!(
+        diff,
+        "--- a\n+++ b\n\
+         @@ -1,5 +1,5 @@\n line 1\n-line 2\n+changed 2\n line 3\n line 4\n line 5\n\
+         @@ -16,5 +16,6 @@\n line 16\n line 17\n line 18\n+inserted\n line 19\n line 20\n"
+    );
+}
+
+#[test]
+fn empty_sides() {
+    assert_eq!(diff("", "a\nb"), "--- a\n+++ b\n@@ -0,0 +1,2 @@\n+a\n+b\n");
+    assert_eq!(diff("a", ""), "--- a\n+++ b\n@@ -1,1 +0,0 @@\n-a\n");
+}
+
+#[src_embed::src_embed]
+pub struct Version1 {
+    pub x: u8,
+}
+
+#[src_embed::src_embed]
+pub struct Version2 {
+    pub x: u8,
+}
+
+#[test]
+fn embedded_sources_can_be_compared() {
+    let diff = diff(__VERSION1_SOURCE__, __VERSION2_SOURCE__);
+    assert!(diff.contains("-pub struct Version1"));
+    assert!(diff.contains("+pub struct Version2"));
+}
+
+#[test]
+fn line_ending_differences_are_shown() {
+    assert_eq!(
+        diff("a\r\nb\r\n", "a\nb\n"),
+        "--- a\n+++ b\n@@ -1,2 +1,2 @@\n-a\n\\ CRLF line ending\n-b\n\\ CRLF line ending\n+a\n+b\n"
+    );
+    assert_eq!(
+        diff("a\nb", "a\nb\n"),
+        "--- a\n+++ b\n@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n+b\n"
+    );
+}
@@ -0,0 +1,77 @@
+//! `src_embed_all!` embeds every item of a block.
+
+pub struct Unit;
+
+pub mod plain {
+    use src_embed::src_embed_all;
+
+    src_embed_all! {
+        pub struct Point {
+            pub x: i32,
+        }
+
+        /// Documented.
+        pub fn origin() -> Point {
+            Point { x: 0 }
+        }
+
+        #[src_embed(name = "RENAMED", raw)]
+        pub enum Axis {
+            X,
+        }
+    }
+}
+
+pub mod deduplicated {
+    use super::Unit;
+    use src_embed::src_embed_all;
+
+    src_embed_all! {
+        #![dedup]
+
+        #[src_embed(name = "FIRST", as_static)]
+        impl Unit {}
+
+        #[src_embed(name = "SECOND", as_static)]
+        impl Unit {}
+
+        #[src_embed(name = "GATED", as_static, feature = "std")]
+        impl Unit {}
+
+        #[src_embed(name = "CONST")]
+        impl Unit {}
+    }
+}
+
+#[test]
+fn every_item_is_embedded() {
+    let _ = (plain::origin().x, plain::Axis::X);
+    assert_eq!(plain::__POINT_SOURCE__, "pub struct Point { pub x: i32, }");
+    assert_eq!(
+        plain::__ORIGIN_SOURCE__,
+        "/// Documented.\npub fn origin() -> Point { Point { x: 0 } }"
+    );
+    assert!(plain::__ORIGIN_QUALIFIERS__.is_empty());
+}
+
+#[test]
+fn item_attributes_supply_arguments() {
+    assert_eq!(
+        plain::__RENAMED_SOURCE__,
+        "pub enum Axis {
+            X,
+        }"
+    );
+}
+
+#[test]
+fn dedup_aliases_identical_sources() {
+    assert_eq!(deduplicated::__SECOND_SOURCE__, "impl Unit {}");
+    assert!(std::ptr::eq(
+        deduplicated::__FIRST_SOURCE__.as_ptr(),
+        deduplicated::__SECOND_SOURCE__.as_ptr()
+    ));
+    // Different gates or kinds keep their own copy.
+    assert_eq!(deduplicated::__GATED_SOURCE__, "impl Unit {}");
+    assert_eq!(deduplicated::__CONST_SOURCE__, "impl Unit {}");
+}
@@ -0,0 +1,71 @@
+//! Runtime values of embedded sources that contain control characters.
+//!
+//! These items deliberately contain a literal tab, a literal form feed and a
+//! string literal spanning several lines, so the embedded constants must carry
+//! those characters through unchanged.
+
+use src_embed::src_embed;
+
+#[src_embed]
+pub fn tab() -> &'static str {
+    "a	b"
+}
+
+#[src_embed]
+pub fn form_feed() -> &'static str {
+    "ab"
+}
+
+#[src_embed]
+pub fn escapes() -> &'static str {
+    "nul: \0, tab: \t"
+}
+
+#[src_embed]
+pub fn line_endings() -> &'static str {
+    "one
+two\r\nthree\rfour"
+}
+
+#[src_embed]
+pub fn quotes() -> [&'static str; 3] {
+    [
+        "say \"hi\"",
+        r#"a "quoted" \path"#,
+        r##"ends with "# inside"##,
+    ]
+}
+
+#[test]
+fn literal_tab_is_preserved() {
+    assert!(__TAB_SOURCE__.contains("\"a\tb\""));
+    assert!(!__TAB_SOURCE__.contains("\\t"));
+}
+
+#[test]
+fn other_control_characters_are_preserved() {
+    assert!(__FORM_FEED_SOURCE__.contains("\"a\u{c}b\""));
+}
+
+#[test]
+fn escape_sequences_stay_escaped() {
+    // The source spells the escapes out, so the embedded text must contain
+    // the backslashes rather than the characters they denote.
+    assert!(__ESCAPES_SOURCE__.contains(r#""nul: \0, tab: \t""#));
+    assert!(!__ESCAPES_SOURCE__.contains('\0'));
+    assert!(!__ESCAPES_SOURCE__.contains('\t'));
+}
+
+#[test]
+fn mixed_line_endings_are_preserved() {
+    assert!(__LINE_ENDINGS_SOURCE__.contains("\"one\ntwo\\r\\nthree\\rfour\""));
+    assert!(!__LINE_ENDINGS_SOURCE__.contains('\r'));
+}
+
+#[test]
+fn quotes_and_hashes_are_preserved() {
+    assert_eq!(quotes()[2], "ends with \"# inside");
+    assert!(__QUOTES_SOURCE__.contains(r#""say \"hi\"""#));
+    assert!(__QUOTES_SOURCE__.contains(r##"r#"a "quoted" \path"#"##));
+    assert!(__QUOTES_SOURCE__.contains(r###"r##"ends with "# inside"##"###));
+}
@@ -0,0 +1 @@
+pub struct Shape { pub sides: u8, }
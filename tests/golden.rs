@@ -0,0 +1,189 @@
+//! `golden` embeds the formatted item with a trailing newline, so it
+//! compares equal to a checked-in snapshot.
+
+// Written unformatted on purpose.
+#[rustfmt::skip]
+mod written {
+    use src_embed::src_embed;
+
+    #[src_embed(golden)]
+    pub struct Point { pub x: i32, pub y: i32 }
+
+    #[src_embed(golden, name = "PointImpl")]
+    impl Point { pub fn origin() -> Self { Point { x: 0, y: 0 } } }
+}
+
+// Written unformatted on purpose, and formatted by rustfmt in the fixtures.
+#[rustfmt::skip]
+mod where_clauses {
+    use src_embed::src_embed;
+
+    #[src_embed(golden)]
+    pub fn make<T>() -> T where T: Default { T::default() }
+
+    #[src_embed(golden)]
+    pub fn both<A, B>(a: A, b: B) -> (A, B) where A: Clone, B: Into<Vec<u8>> { (a, b) }
+
+    #[src_embed(golden)]
+    pub fn call<F>(f: F) -> u8 where F: Fn(u8) -> u8 { f(1) }
+
+    #[src_embed(golden)]
+    pub fn nothing<T>(_: T) where T: Copy {}
+
+    #[src_embed(golden)]
+    pub struct Wrap<T> where T: Clone { pub value: T }
+
+    #[src_embed(golden)]
+    pub struct Tuple<T>(pub T) where T: Copy;
+
+    #[src_embed(golden)]
+    pub trait Show where Self: Sized { fn show(&self) -> String; fn with<T>(&self, t: T) where T: Default; }
+
+    #[src_embed(golden, name = "WRAP_IMPL")]
+    impl<T> Wrap<T> where T: Clone + Default { pub fn empty() -> Self { Self::new(T::default()) } pub fn new(value: T) -> Self { Wrap { value } } }
+}
+
+#[rustfmt::skip]
+mod match_arms {
+    use src_embed::src_embed;
+
+    #[src_embed(golden)]
+    pub fn kind(n: u8) -> &'static str { match n { 0 => "zero", 1 => "one", _ => "many", } }
+
+    #[src_embed(golden)]
+    pub fn check(n: u8) { match n { 0 => println!("zero"), 1 => { println!("one"); } _ => {} } }
+
+    #[src_embed(golden)]
+    pub fn skip(n: Option<u8>) { if let Some(n) = n { match n { 0 => {} _ => { println!("{}", n); } } } }
+}
+
+#[rustfmt::skip]
+mod closures {
+    use src_embed::src_embed;
+
+    #[src_embed(golden, name = "LIMIT")]
+    pub const LIMIT: u32 = 10;
+
+    #[src_embed(golden)]
+    pub fn over(values: &[u32]) -> Vec<u32> { values.iter().filter(|x| LIMIT < **x).copied().collect() }
+
+    #[src_embed(golden)]
+    pub fn negated(values: &[i32]) -> Vec<i32> { values.iter().map(|x| -*x).collect() }
+
+    #[src_embed(golden)]
+    pub fn none_set(flags: &[bool]) -> bool { flags.iter().all(|flag| !*flag) }
+
+    #[src_embed(golden)]
+    pub fn sized<const N: usize, const M: usize>() -> bool { N < M && LIMIT > 1 }
+}
+
+use written::*;
+
+impl where_clauses::Show for u8 {
+    fn show(&self) -> String {
+        self.to_string()
+    }
+    fn with<T>(&self, _: T)
+    where
+        T: Default,
+    {
+    }
+}
+
+#[test]
+fn matches_snapshots_without_trimming() {
+    let origin = Point::origin();
+    assert_eq!((origin.x, origin.y), (0, 0));
+    assert_eq!(__POINT_SOURCE__, include_str!("fixtures/point.golden"));
+    assert_eq!(__POINT_EMBED_OPTS__, "golden");
+}
+
+#[test]
+fn impls_match_snapshots() {
+    assert_eq!(
+        __POINTIMPL_SOURCE__,
+        include_str!("fixtures/point_impl.golden")
+    );
+    assert!(!__POINTIMPL_SOURCE__.ends_with("\n\n"));
+}
+
+#[test]
+fn where_clauses_match_rustfmt() {
+    use where_clauses::*;
+
+    assert_eq!(make::<u8>(), 0);
+    assert_eq!(both(1, "b"), (1, "b"));
+    assert_eq!(call(|n| n + 1), 2);
+    nothing(0);
+    assert_eq!(Tuple(1).0, Wrap::<u8>::empty().value + 1);
+    assert_eq!(1.show(), "1");
+    1.with(0);
+    assert_eq!(
+        [
+            __MAKE_SOURCE__,
+            __BOTH_SOURCE__,
+            __CALL_SOURCE__,
+            __NOTHING_SOURCE__,
+            __WRAP_SOURCE__,
+            __TUPLE_SOURCE__,
+            __SHOW_SOURCE__,
+            __WRAP_IMPL_SOURCE__,
+        ]
+        .concat(),
+        include_str!("fixtures/where_clauses.golden")
+    );
+}
+
+#[test]
+fn match_arms_match_rustfmt() {
+    use match_arms::*;
+
+    assert_eq!(kind(1), "one");
+    check(2);
+    skip(None);
+    assert_eq!(
+        [__KIND_SOURCE__, __CHECK_SOURCE__, __SKIP_SOURCE__].concat(),
+        include_str!("fixtures/match_arms.golden")
+    );
+}
+
+#[test]
+fn closures_and_comparisons_match_rustfmt() {
+    use closures::*;
+
+    assert_eq!(over(&[5, 20]), [20]);
+    assert_eq!(negated(&[1]), [-1]);
+    assert!(none_set(&[false]));
+    assert!(sized::<1, 2>());
+    assert_eq!(
+        [
+            __LIMIT_SOURCE__,
+            __OVER_SOURCE__,
+            __NEGATED_SOURCE__,
+            __NONE_SET_SOURCE__,
+            __SIZED_SOURCE__,
+        ]
+        .concat(),
+        include_str!("fixtures/closures.golden")
+    );
+}
+
+#[test]
+fn fixtures_are_formatted_by_rustfmt() {
+    let fixtures = ["closures", "match_arms", "where_clauses"];
+    for fixture in fixtures {
+        let path = format!(
+            "{}/tests/fixtures/{}.golden",
+            env!("CARGO_MANIFEST_DIR"),
+            fixture
+        );
+        let status = std::process::Command::new("rustfmt")
+            .args(["--edition", "2021", "--check", &path])
+            .status();
+        // Nothing to compare with on toolchains without rustfmt.
+        let Ok(status) = status else {
+            return;
+        };
+        assert!(status.success(), "rustfmt would change {}", path);
+    }
+}
@@ -0,0 +1,87 @@
+//! `group_by_kind` on a module collects its items' constants by kind.
+
+use src_embed::src_embed;
+
+#[src_embed(group_by_kind)]
+pub mod catalog {
+    // The attributes below are expanded by `group_by_kind`, so they need no
+    // import of their own.
+    #[src_embed]
+    pub struct Point {
+        pub x: i32,
+    }
+
+    #[src_embed(raw)]
+    pub enum Axis {
+        X,
+        Y,
+    }
+
+    #[src_embed]
+    pub fn origin() -> Point {
+        Point { x: 0 }
+    }
+
+    #[src_embed]
+    pub trait Area {
+        fn area(&self) -> f64;
+    }
+
+    #[src_embed]
+    impl Area for Point {
+        fn area(&self) -> f64 {
+            0.0
+        }
+    }
+
+    #[src_embed]
+    pub type Coordinate = i32;
+
+    /// Items without the attribute are left alone.
+    pub struct Plain;
+
+    #[src_embed(group_by_kind)]
+    pub mod nested {
+        #[src_embed::src_embed]
+        pub struct Inner;
+    }
+}
+
+#[test]
+fn constants_live_in_one_module_per_kind() {
+    let _ = (
+        catalog::origin().x,
+        catalog::Axis::X,
+        catalog::Axis::Y,
+        catalog::Plain,
+    );
+    assert_eq!(
+        catalog::struct_sources::__POINT_SOURCE__,
+        "pub struct Point { pub x: i32, }"
+    );
+    assert!(catalog::enum_sources::__AXIS_SOURCE__.starts_with("pub enum Axis"));
+    assert!(catalog::fn_sources::__ORIGIN_SOURCE__.starts_with("pub fn origin"));
+    assert!(catalog::fn_sources::__ORIGIN_QUALIFIERS__.is_empty());
+    assert!(catalog::trait_sources::__AREA_SOURCE__.starts_with("pub trait Area"));
+    assert!(catalog::impl_sources::__POINT_SOURCE__.starts_with("impl Area for Point"));
+    assert!(catalog::item_sources::__ITEM_SOURCE__.starts_with("pub type Coordinate"));
+}
+
+#[test]
+fn item_arguments_still_apply() {
+    assert_eq!(
+        catalog::enum_sources::__AXIS_SOURCE__,
+        "pub enum Axis {
+        X,
+        Y,
+    }"
+    );
+}
+
+#[test]
+fn modules_nest() {
+    assert_eq!(
+        catalog::nested::struct_sources::__INNER_SOURCE__,
+        "pub struct Inner;"
+    );
+}
@@ -0,0 +1,34 @@
+//! `header = "checksum"` prepends a SHA-256 comment to the embedded source.
+
+use src_embed::src_embed;
+
+#[src_embed(header = "checksum")]
+pub struct Foo;
+
+#[src_embed(header = "checksum")]
+pub fn spans_several_blocks(values: &[u32]) -> u32 {
+    values.iter().copied().filter(|value| value % 2 == 0).sum()
+}
+
+#[test]
+fn header_is_the_first_line() {
+    assert_eq!(
+        __FOO_SOURCE__,
+        "// sha: f6a164b281f0d67735d771bb7ca8642f83cdb92163c08cfb3229c985c2589a37\npub struct Foo;"
+    );
+}
+
+#[test]
+fn hash_covers_the_source_after_the_header() {
+    let (header, source) = __SPANS_SEVERAL_BLOCKS_SOURCE__
+        .split_once('\n')
+        .expect("header line");
+    // The expected digest is that of the compiler's rendering of the item,
+    // which is longer than one SHA-256 block.
+    assert!(source.starts_with("pub fn spans_several_blocks"));
+    assert!(source.len() > 64);
+    assert_eq!(
+        header,
+        "// sha: c6bd4a23194d219a49001fc27398535ad120368de61d0bc1a75d5930b6445260"
+    );
+}
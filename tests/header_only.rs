@@ -0,0 +1,74 @@
+//! `header_only` embeds the declaration of a type without its body.
+
+use std::fmt::Debug;
+
+use src_embed::src_embed;
+
+/// Not part of the header.
+#[src_embed(header_only)]
+#[derive(Debug)]
+pub struct Config<'a, T: Debug = u8> {
+    pub name: &'a str,
+    pub value: T,
+    retries: u32,
+}
+
+#[src_embed(header_only)]
+pub struct Meters(pub f64);
+
+#[src_embed(header_only)]
+pub struct Wrapper<T>(pub T, pub T)
+where
+    T: Clone;
+
+#[src_embed(header_only)]
+pub struct Unit;
+
+// Written with the `where` clause inline on purpose.
+#[rustfmt::skip]
+mod inline_where {
+    use src_embed::src_embed;
+
+    #[src_embed(header_only)]
+    pub(crate) enum Axis<T> where T: Copy {
+        X(T),
+        Y(T),
+        Z,
+    }
+}
+
+use inline_where::{Axis, __AXIS_SOURCE__};
+
+#[test]
+fn named_struct_counts_its_fields() {
+    let config = Config {
+        name: "a",
+        value: 1u8,
+        retries: 0,
+    };
+    assert_eq!(config.retries, 0);
+    assert_eq!(
+        __CONFIG_SOURCE__,
+        "pub struct Config<'a, T: Debug = u8> { /* 3 fields */ }"
+    );
+}
+
+#[test]
+fn tuple_and_unit_structs_keep_their_shape() {
+    let _ = (Meters(1.0).0, Wrapper(1, 2).1, Unit);
+    assert_eq!(__METERS_SOURCE__, "pub struct Meters(/* 1 field */);");
+    assert_eq!(
+        __WRAPPER_SOURCE__,
+        "pub struct Wrapper<T>(/* 2 fields */) where T: Clone;"
+    );
+    assert_eq!(__UNIT_SOURCE__, "pub struct Unit;");
+}
+
+#[test]
+fn enum_counts_its_variants() {
+    let _ = [Axis::X(1), Axis::Y(2), Axis::Z];
+    assert_eq!(
+        __AXIS_SOURCE__,
+        "pub(crate) enum Axis<T> where T: Copy { /* 3 variants */ }"
+    );
+}
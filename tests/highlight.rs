@@ -0,0 +1,69 @@
+//! `highlight` files the item's identifiers by their role in the syntax.
+#![cfg(feature = "highlight")]
+
+use src_embed::src_embed;
+
+#[src_embed(highlight)]
+pub fn area<'a, T: AsRef<str> + 'a>(shape: &'a Shape, label: T) -> Option<f64> {
+    let scale = SCALE;
+    let name = label.as_ref();
+    println!("{name}");
+    match shape {
+        Shape::Circle(radius) => Some(radius * radius * scale),
+        Shape::Rect { width, height } => Some(compute(*width, *height)),
+        Shape::Empty => None,
+    }
+}
+
+pub const SCALE: f64 = 3.0;
+
+pub enum Shape {
+    Circle(f64),
+    Rect { width: f64, height: f64 },
+    Empty,
+}
+
+fn compute(width: f64, height: f64) -> f64 {
+    width * height
+}
+
+#[src_embed(highlight)]
+pub struct Labeled<'a> {
+    pub label: &'a str,
+    pub shape: Shape,
+}
+
+#[test]
+fn identifiers_are_filed_by_role() {
+    assert_eq!(
+        __AREA_HIGHLIGHT__,
+        concat!(
+            r#"{"types":["T","str","Shape","Option","f64"],"#,
+            r#""traits":["AsRef"],"#,
+            r#""functions":["area","as_ref","compute"],"#,
+            r#""fields":["width","height"],"#,
+            r#""variants":["Circle","Some","Rect","Empty","None"],"#,
+            r#""values":["shape","label","scale","SCALE","name","radius","width","height"],"#,
+            r#""lifetimes":["'a"],"#,
+            r#""macros":["println"]}"#,
+        )
+    );
+    assert!(area(&Shape::Empty, "x").is_none());
+}
+
+#[test]
+fn every_category_is_present() {
+    let label = Labeled {
+        label: "",
+        shape: Shape::Circle(1.0),
+    };
+    assert!(label.label.is_empty() && matches!(label.shape, Shape::Circle(_)));
+    assert_eq!(
+        __LABELED_HIGHLIGHT__,
+        concat!(
+            r#"{"types":["Labeled","str","Shape"],"traits":[],"functions":[],"#,
+            r#""fields":["label","shape"],"variants":[],"values":[],"#,
+            r#""lifetimes":["'a"],"macros":[]}"#,
+        )
+    );
+}
@@ -0,0 +1,23 @@
+//! `__FOO_SOURCE_LANG__` labels the language of the embedded snippet.
+
+use src_embed::src_embed;
+
+#[src_embed]
+pub struct Plain;
+
+#[src_embed(lang = "rust-2024")]
+pub struct Tagged;
+
+#[test]
+fn lang_defaults_to_rust() {
+    let _ = Plain;
+    assert_eq!(__PLAIN_SOURCE_LANG__, "rust");
+    assert_eq!(__PLAIN_EMBED_OPTS__, "");
+}
+
+#[test]
+fn lang_argument_sets_the_label() {
+    let _ = Tagged;
+    assert_eq!(__TAGGED_SOURCE_LANG__, "rust-2024");
+    assert_eq!(__TAGGED_EMBED_OPTS__, "lang = \"rust-2024\"");
+}
@@ -0,0 +1,48 @@
+//! Very large items embed in time and memory proportional to their size.
+
+use src_embed::src_embed;
+
+/// Expands to `big()`, summing `1` once per statement: one statement
+/// doubled once for every `x`.
+macro_rules! big {
+    ($total:ident; [$($statements:tt)*]) => {
+        #[src_embed]
+        pub fn big() -> u64 {
+            let mut $total = 0;
+            $($statements)*
+            $total
+        }
+    };
+    ($total:ident; x $($rest:ident)* [$($statements:tt)*]) => {
+        big!($total; $($rest)* [$($statements)* $($statements)*]);
+    };
+}
+
+big!(total; x x x x x x x x x x x x [total += 1;]);
+
+/// A function of `lines` statements, one per line.
+fn generated(lines: usize) -> String {
+    let mut text = String::from("pub fn generated() -> u64 {\n    let mut total = 0u64;\n");
+    for i in 0..lines {
+        text.push_str(&format!(
+            "    total += \"{}\".len() as u64; // line {}\n",
+            i, i
+        ));
+    }
+    text.push_str("    total\n}\n");
+    text
+}
+
+#[test]
+fn macro_generated_item_with_thousands_of_statements() {
+    assert_eq!(big(), 4096);
+    assert_eq!(__BIG_SOURCE__.matches("+=").count(), 4096);
+}
+
+#[test]
+fn fifty_thousand_line_item() {
+    let text = generated(50_000);
+    let expanded = src_embed_core::expand("".parse().unwrap(), text.parse().unwrap()).to_string();
+    assert!(expanded.contains("pub const __GENERATED_SOURCE__ : & str ="));
+    assert!(expanded.contains("\"49999\""));
+}
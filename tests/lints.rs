@@ -0,0 +1,34 @@
+//! The attributes of the generated constants: `#[allow(dead_code)]` unless
+//! `allow_dead_code = false`, `#[doc(hidden)]` unless `visible_docs`.
+#![deny(dead_code, missing_docs)]
+
+mod private {
+    use src_embed::src_embed;
+
+    /// Embedded, but neither the struct nor its constants are ever used.
+    #[src_embed]
+    #[allow(dead_code)]
+    pub struct Unused;
+}
+
+/// Public, so `missing_docs` applies to the visible constants.
+pub mod checked {
+    use src_embed::src_embed;
+
+    /// Only used through its constants.
+    #[src_embed(allow_dead_code = false, visible_docs)]
+    pub struct Checked;
+}
+
+#[test]
+fn unused_constants_are_allowed() {
+    assert_eq!(
+        checked::__CHECKED_SOURCE__,
+        "/// Only used through its constants.\npub struct Checked;"
+    );
+    assert_eq!(
+        checked::__CHECKED_EMBED_OPTS__,
+        "allow_dead_code = false, visible_docs"
+    );
+    assert_eq!(checked::__CHECKED_SOURCE_LANG__, "rust");
+}
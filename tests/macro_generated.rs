@@ -0,0 +1,47 @@
+//! Items produced by `macro_rules!` expansions embed their expanded tokens.
+//!
+//! Such items have no backing text of their own in the source file, so the
+//! embedded source must come from the tokens the declarative macro produced.
+
+use src_embed::src_embed;
+
+macro_rules! getter {
+    ($name:ident, $value:expr) => {
+        #[src_embed]
+        pub fn $name() -> u32 {
+            $value
+        }
+    };
+}
+
+getter!(answer, 40 + 2);
+
+macro_rules! passthrough {
+    ($item:item) => {
+        $item
+    };
+}
+
+passthrough! {
+    #[src_embed]
+    pub struct Forwarded {
+        pub value: u32,
+    }
+}
+
+#[test]
+fn attribute_inside_macro_definition() {
+    assert_eq!(answer(), 42);
+    // Spacing around substituted fragments is up to the compiler, so only
+    // the tokens themselves are checked.
+    assert!(__ANSWER_SOURCE__.starts_with("pub fn answer"));
+    assert!(__ANSWER_SOURCE__.contains("-> u32"));
+    assert!(__ANSWER_SOURCE__.contains("40 + 2"));
+}
+
+#[test]
+fn attribute_forwarded_through_macro() {
+    let _ = Forwarded { value: 1 }.value;
+    assert!(__FORWARDED_SOURCE__.starts_with("pub struct Forwarded"));
+    assert!(__FORWARDED_SOURCE__.contains("pub value: u32"));
+}
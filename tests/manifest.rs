@@ -0,0 +1,128 @@
+//! `src_embed::manifest()` lists every embedded item of the program.
+#![cfg(feature = "manifest")]
+
+use src_embed::{src_embed, src_embed_all};
+
+#[src_embed]
+pub struct Listed;
+
+#[src_embed(name = "Renamed")]
+pub enum Axis {
+    X,
+}
+
+pub struct Counter;
+
+#[src_embed]
+impl Counter {
+    #[src_embed]
+    pub fn reset() {}
+}
+
+pub mod block {
+    use src_embed::src_embed_all;
+
+    src_embed_all! {
+        pub fn first() {}
+        pub fn second() {}
+    }
+}
+
+#[allow(unexpected_cfgs)]
+pub mod disabled {
+    #[src_embed::src_embed(feature = "not_a_feature")]
+    pub struct Disabled;
+}
+
+src_embed_all! {
+    pub trait Shape {}
+}
+
+fn entry(name: &str) -> Option<(String, String)> {
+    src_embed::manifest()
+        .into_iter()
+        .find(|(entry, _, _)| entry == name)
+        .map(|(_, kind, source)| (kind, source))
+}
+
+#[test]
+fn lists_annotated_items() {
+    assert_eq!(
+        entry("Listed"),
+        Some(("struct".to_string(), __LISTED_SOURCE__.to_string()))
+    );
+    assert_eq!(
+        entry("Renamed"),
+        Some(("enum".to_string(), __RENAMED_SOURCE__.to_string()))
+    );
+    assert_eq!(
+        entry("Counter").map(|(kind, _)| kind).as_deref(),
+        Some("impl")
+    );
+}
+
+#[test]
+fn lists_associated_items() {
+    assert_eq!(
+        entry("reset"),
+        Some(("fn".to_string(), Counter::__RESET_SOURCE__.to_string()))
+    );
+}
+
+#[test]
+fn lists_block_items() {
+    assert_eq!(
+        entry("first"),
+        Some(("fn".to_string(), block::__FIRST_SOURCE__.to_string()))
+    );
+    assert!(entry("second").is_some());
+    assert_eq!(
+        entry("Shape").map(|(kind, _)| kind).as_deref(),
+        Some("trait")
+    );
+}
+
+#[test]
+fn skips_disabled_items() {
+    assert_eq!(entry("Disabled"), None);
+}
+
+#[test]
+fn lists_each_item_once() {
+    let names: Vec<String> = src_embed::manifest()
+        .into_iter()
+        .map(|(name, _, _)| name)
+        .collect();
+    assert_eq!(names.iter().filter(|name| *name == "Listed").count(), 1);
+}
+
+#[test]
+fn lookup_finds_sources_by_name() {
+    assert_eq!(src_embed::lookup("Listed"), Some(__LISTED_SOURCE__));
+    assert_eq!(src_embed::lookup("Renamed"), Some(__RENAMED_SOURCE__));
+    assert_eq!(src_embed::lookup("Axis"), None);
+    assert_eq!(src_embed::lookup("Disabled"), None);
+    assert_eq!(src_embed::lookup("first"), Some(block::__FIRST_SOURCE__));
+}
+
+#[test]
+fn sorted_manifest_is_ordered() {
+    use src_embed::ManifestOrder;
+
+    let by_name = src_embed::manifest_sorted(ManifestOrder::Name);
+    assert_eq!(by_name.len(), src_embed::manifest().len());
+    assert!(by_name.windows(2).all(|pair| pair[0] <= pair[1]));
+
+    let by_kind = src_embed::manifest_sorted(ManifestOrder::KindThenName);
+    let keys: Vec<(&str, &str)> = by_kind
+        .iter()
+        .map(|(name, kind, _)| (kind.as_str(), name.as_str()))
+        .collect();
+    assert!(keys.windows(2).all(|pair| pair[0] <= pair[1]));
+    let fns: Vec<&str> = keys
+        .iter()
+        .filter(|(kind, _)| *kind == "fn")
+        .map(|(_, name)| *name)
+        .collect();
+    assert_eq!(fns, ["first", "reset", "second"]);
+}
@@ -0,0 +1,270 @@
+//! Structured metadata constants generated alongside the source constant.
+
+use std::rc::Rc;
+
+use src_embed::src_embed;
+
+#[src_embed]
+pub fn plain() {}
+
+#[src_embed]
+pub async fn asynchronous() {}
+
+/// # Safety
+/// Always safe to call.
+#[src_embed]
+pub const unsafe fn const_unsafe() {}
+
+#[src_embed]
+pub extern "C" fn with_abi() {}
+
+/// # Safety
+/// Always safe to call.
+#[src_embed]
+pub unsafe extern "C" fn unsafe_with_abi() {}
+
+#[test]
+fn fn_qualifiers() {
+    assert!(__PLAIN_QUALIFIERS__.is_empty());
+    assert_eq!(__ASYNCHRONOUS_QUALIFIERS__, ["async"]);
+    assert_eq!(__CONST_UNSAFE_QUALIFIERS__, ["const", "unsafe"]);
+    assert_eq!(__WITH_ABI_QUALIFIERS__, ["extern \"C\""]);
+    assert_eq!(__UNSAFE_WITH_ABI_QUALIFIERS__, ["unsafe", "extern \"C\""]);
+}
+
+pub struct Counter(u32);
+
+pub mod counter_impl {
+    use super::{src_embed, Counter};
+
+    #[src_embed]
+    pub fn bump(counter: &mut Counter, (by, times): (u32, u32)) -> &mut Counter {
+        counter.0 += by * times;
+        counter
+    }
+}
+
+#[src_embed]
+pub fn lookup<'a, T: AsRef<str>>(items: &'a [T], mut index: usize, default: &'a str) -> &'a str {
+    index %= items.len().max(1);
+    items.get(index).map_or(default, AsRef::as_ref)
+}
+
+#[src_embed]
+pub fn boxed(#[allow(unused_variables)] unused: u8) -> Box<dyn Fn() -> Vec<u8> + Send> {
+    Box::new(Vec::new)
+}
+
+impl Counter {
+    #[src_embed]
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+
+    #[src_embed]
+    pub fn into_inner(self: Rc<Self>) -> u32 {
+        self.0
+    }
+}
+
+#[test]
+fn fn_params() {
+    assert!(__PLAIN_PARAMS__.is_empty());
+    assert_eq!(
+        counter_impl::__BUMP_PARAMS__,
+        ["counter: &mut Counter", "(by, times): (u32, u32)"]
+    );
+    assert_eq!(
+        __LOOKUP_PARAMS__,
+        ["items: &'a [T]", "mut index: usize", "default: &'a str"]
+    );
+    assert_eq!(__BOXED_PARAMS__, ["unused: u8"]);
+    assert_eq!(Counter::__GET_PARAMS__, ["&self"]);
+    assert_eq!(Counter::__INTO_INNER_PARAMS__, ["self: Rc<Self>"]);
+}
+
+#[test]
+fn fn_return_type() {
+    assert_eq!(__PLAIN_RETURN__, "()");
+    assert_eq!(counter_impl::__BUMP_RETURN__, "&mut Counter");
+    assert_eq!(__LOOKUP_RETURN__, "&'a str");
+    assert_eq!(__BOXED_RETURN__, "Box<dyn Fn() -> Vec<u8> + Send>");
+    assert_eq!(Counter::__GET_RETURN__, "u32");
+    assert_eq!(Counter::__INTO_INNER_RETURN__, "u32");
+}
+
+pub mod options {
+    use src_embed::src_embed;
+
+    #[src_embed]
+    pub struct Defaults;
+
+    #[src_embed(raw, as_static, name = "Reordered", pretty)]
+    pub struct Written;
+
+    #[src_embed(feature = "std", header = "checksum")]
+    pub struct Valued;
+}
+
+#[test]
+fn embed_opts_are_normalized() {
+    assert_eq!(options::__DEFAULTS_EMBED_OPTS__, "");
+    assert_eq!(
+        options::__REORDERED_EMBED_OPTS__,
+        "name = \"Reordered\", as_static, raw, pretty"
+    );
+    assert_eq!(
+        options::__VALUED_EMBED_OPTS__,
+        "feature = \"std\", header = \"checksum\""
+    );
+}
+
+#[test]
+fn embed_opts_parse_back() {
+    let annotated = format!(
+        "#[src_embed({})] struct X;",
+        options::__REORDERED_EMBED_OPTS__
+    );
+    let item: syn::ItemStruct = syn::parse_str(&annotated).expect("valid item");
+    let parsed = src_embed_core::Options::from_attribute(&item.attrs[0]).expect("valid arguments");
+    assert_eq!(parsed.to_args(), options::__REORDERED_EMBED_OPTS__);
+}
+
+pub struct Limits;
+
+pub mod limits_impl {
+    use super::{src_embed, Limits};
+
+    #[src_embed]
+    impl Limits {
+        pub const MAX: u32 = 10 * 1024;
+        pub const NAME: &'static str = "limits";
+        pub const STEPS: [u8; 3] = [1, 2, 3];
+
+        pub fn max() -> u32 {
+            Self::MAX
+        }
+    }
+}
+
+pub mod counter_trait_impl {
+    use super::{src_embed, Counter};
+
+    #[src_embed]
+    impl Default for Counter {
+        fn default() -> Self {
+            Counter(1)
+        }
+    }
+}
+
+#[test]
+fn impl_assoc_consts() {
+    assert_eq!(Limits::max(), Limits::MAX);
+    assert_eq!(
+        limits_impl::__LIMITS_ASSOC_CONSTS__,
+        [
+            ("MAX", "10 * 1024"),
+            ("NAME", "\"limits\""),
+            ("STEPS", "[1, 2, 3]"),
+        ]
+    );
+    assert!(counter_trait_impl::__COUNTER_ASSOC_CONSTS__.is_empty());
+}
+
+pub struct Table<K, V>(pub Vec<(K, V)>);
+
+pub mod table_impl {
+    use super::{src_embed, Table};
+
+    #[src_embed]
+    impl Table<u32, String> {
+        pub fn first_key(&self) -> Option<u32> {
+            self.0.first().map(|(key, _)| *key)
+        }
+    }
+
+    #[src_embed(name = "Pairs")]
+    impl<'a, T> super::Table<&'a str, Vec<T>> {
+        pub fn names(&self) -> Vec<&'a str> {
+            self.0.iter().map(|(name, _)| *name).collect()
+        }
+    }
+}
+
+#[test]
+fn impl_args() {
+    assert_eq!(Table(vec![(7, String::new())]).first_key(), Some(7));
+    assert_eq!(table_impl::__TABLE_IMPL_ARGS__, ["u32", "String"]);
+    assert_eq!(Table::<&str, Vec<u8>>(vec![("a", vec![])]).names(), ["a"]);
+    assert_eq!(table_impl::__PAIRS_IMPL_ARGS__, ["&'a str", "Vec<T>"]);
+    assert!(limits_impl::__LIMITS_IMPL_ARGS__.is_empty());
+}
+
+#[src_embed]
+pub struct Buffer<'a, T: Copy = u8, const N: usize = { 4 * 16 }, U = Vec<&'static str>> {
+    pub data: [T; N],
+    pub label: &'a str,
+    pub extra: Option<U>,
+}
+
+#[src_embed]
+pub enum Either<L, R = L> {
+    Left(L),
+    Right(R),
+}
+
+#[src_embed]
+pub trait Decode<Out = Self> {
+    fn decode(&self) -> Out;
+}
+
+#[src_embed]
+pub trait Named {
+    fn name(&self) -> String;
+
+    fn describe(&self) -> String {
+        format!("<{}>", self.name())
+    }
+
+    fn shout(&self) -> String {
+        self.name().to_uppercase()
+    }
+}
+
+impl Named for Counter {
+    fn name(&self) -> String {
+        format!("counter {}", self.0)
+    }
+}
+
+#[test]
+fn trait_defaults() {
+    assert_eq!(Counter(2).describe(), "<counter 2>");
+    assert_eq!(Counter(2).shout(), "COUNTER 2");
+    assert_eq!(
+        __NAMED_DEFAULTS__,
+        [
+            ("describe", "{ format!(\"<{}>\", self.name()) }"),
+            ("shout", "{ self.name().to_uppercase() }"),
+        ]
+    );
+    assert!(__DECODE_DEFAULTS__.is_empty());
+}
+
+#[test]
+fn generic_defaults() {
+    let buffer: Buffer = Buffer {
+        data: [0; 64],
+        label: "",
+        extra: None,
+    };
+    assert_eq!(buffer.data.len(), 64);
+    assert_eq!(
+        __BUFFER_GENERIC_DEFAULTS__,
+        [("T", "u8"), ("N", "{ 4 * 16 }"), ("U", "Vec<&'static str>")]
+    );
+    let _: Either<u8> = Either::Right(1);
+    assert_eq!(__EITHER_GENERIC_DEFAULTS__, [("R", "L")]);
+    assert_eq!(__DECODE_GENERIC_DEFAULTS__, [("Out", "Self")]);
+}
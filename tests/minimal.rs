@@ -0,0 +1,67 @@
+//! Degenerate but valid items embed their exact, minimal source.
+
+use src_embed::src_embed;
+
+#[src_embed]
+pub struct Unit;
+
+#[src_embed]
+pub struct Empty {}
+
+#[src_embed]
+pub struct EmptyTuple();
+
+#[src_embed]
+pub enum Never {}
+
+#[src_embed]
+pub trait Marker {}
+
+#[src_embed]
+pub fn noop() {}
+
+mod empty_impl {
+    use super::{src_embed, Unit};
+
+    #[src_embed]
+    impl Unit {}
+}
+
+mod marker_impl {
+    use super::{src_embed, Marker, Unit};
+
+    #[src_embed]
+    impl Marker for Unit {}
+}
+
+#[test]
+fn unit_struct() {
+    assert_eq!(__UNIT_SOURCE__, "pub struct Unit;");
+}
+
+#[test]
+fn empty_structs() {
+    assert_eq!(__EMPTY_SOURCE__, "pub struct Empty {}");
+    assert_eq!(__EMPTYTUPLE_SOURCE__, "pub struct EmptyTuple();");
+}
+
+#[test]
+fn empty_enum() {
+    assert_eq!(__NEVER_SOURCE__, "pub enum Never {}");
+}
+
+#[test]
+fn marker_trait() {
+    assert_eq!(__MARKER_SOURCE__, "pub trait Marker {}");
+}
+
+#[test]
+fn empty_fn() {
+    assert_eq!(__NOOP_SOURCE__, "pub fn noop() {}");
+}
+
+#[test]
+fn empty_impls() {
+    assert_eq!(empty_impl::__UNIT_SOURCE__, "impl Unit {}");
+    assert_eq!(marker_impl::__UNIT_SOURCE__, "impl Marker for Unit {}");
+}
@@ -0,0 +1,222 @@
+//! Names of the constants generated for different items and arguments.
+
+use src_embed::src_embed;
+
+pub mod foo {
+    pub mod bar {
+        pub struct Baz;
+    }
+}
+
+pub mod last_segment {
+    use super::*;
+
+    #[src_embed]
+    impl foo::bar::Baz {
+        pub fn last_segment() {}
+    }
+}
+
+pub mod full_path {
+    use super::*;
+
+    #[src_embed(full_path)]
+    impl foo::bar::Baz {
+        pub fn full_path() {}
+    }
+}
+
+pub trait Describe {
+    fn describe(&self) -> &'static str;
+}
+
+pub mod leading_colons {
+    use super::*;
+
+    #[src_embed(full_path)]
+    impl Describe for ::std::string::String {
+        fn describe(&self) -> &'static str {
+            "string"
+        }
+    }
+}
+
+pub mod single_segment {
+    use super::*;
+    use foo::bar::Baz;
+
+    #[src_embed(full_path)]
+    impl Baz {
+        pub fn single_segment() {}
+    }
+}
+
+#[test]
+fn impl_defaults_to_last_path_segment() {
+    assert!(last_segment::__BAZ_SOURCE__.contains("fn last_segment"));
+}
+
+#[test]
+fn full_path_joins_every_segment() {
+    assert!(full_path::__FOO_BAR_BAZ_SOURCE__.contains("fn full_path"));
+}
+
+#[test]
+fn full_path_ignores_leading_colons() {
+    assert!(leading_colons::__STD_STRING_STRING_SOURCE__.contains("fn describe"));
+}
+
+#[test]
+fn full_path_with_single_segment_matches_default() {
+    assert!(single_segment::__BAZ_SOURCE__.contains("fn single_segment"));
+}
+
+pub mod renamed {
+    use super::*;
+
+    #[src_embed(name = "point")]
+    pub struct Bar;
+
+    #[src_embed(name = "CHOICE")]
+    pub enum Pick {
+        One,
+    }
+
+    #[src_embed(name = "Entry")]
+    pub fn run() -> u32 {
+        matches!(Pick::One, Pick::One) as u32
+    }
+
+    #[src_embed(name = "SHAPE")]
+    pub trait Shape {}
+
+    #[src_embed(name = "BAR_IMPL")]
+    impl Shape for Bar {}
+
+    #[src_embed(name = "ALIAS")]
+    pub type Alias = Bar;
+
+    pub mod full_path {
+        use super::super::*;
+
+        #[src_embed(full_path, name = "OVERRIDE")]
+        impl foo::bar::Baz {
+            pub fn overridden() {}
+        }
+    }
+}
+
+#[test]
+fn name_overrides_the_derived_name() {
+    assert!(renamed::__POINT_SOURCE__.starts_with("pub struct Bar"));
+    assert!(renamed::__CHOICE_SOURCE__.starts_with("pub enum Pick"));
+    assert!(renamed::__ENTRY_SOURCE__.starts_with("pub fn run"));
+    assert!(renamed::__SHAPE_SOURCE__.starts_with("pub trait Shape"));
+    assert!(renamed::__BAR_IMPL_SOURCE__.starts_with("impl Shape for Bar"));
+    assert!(renamed::__ALIAS_SOURCE__.starts_with("pub type Alias"));
+}
+
+#[test]
+fn name_wins_over_full_path() {
+    assert!(renamed::full_path::__OVERRIDE_SOURCE__.contains("fn overridden"));
+}
+
+#[test]
+fn name_applies_to_metadata_constants() {
+    assert_eq!(renamed::run(), 1);
+    assert!(renamed::__ENTRY_QUALIFIERS__.is_empty());
+    assert_eq!(renamed::__ENTRY_RETURN__, "u32");
+}
+
+pub mod cased {
+    use super::*;
+
+    #[src_embed(case = "upper")]
+    pub struct HttpServer;
+
+    #[src_embed(case = "lower")]
+    pub struct LowerCase;
+
+    #[src_embed(case = "preserve", as_static)]
+    pub struct KeepAsIs;
+
+    #[src_embed(case = "screaming_snake")]
+    pub struct HTTPServerConfig;
+
+    #[src_embed(case = "screaming_snake")]
+    pub fn parse_v2_header() {}
+
+    #[src_embed(case = "screaming_snake", name = "customName")]
+    pub struct Renamed;
+
+    pub mod full_path {
+        use super::super::*;
+
+        #[src_embed(full_path, case = "screaming_snake")]
+        impl foo::bar::Baz {
+            pub fn screaming() {}
+        }
+    }
+}
+
+#[test]
+fn case_applies_to_the_name_segment_only() {
+    assert!(cased::__HTTPSERVER_SOURCE__.starts_with("pub struct HttpServer"));
+    assert!(cased::__lowercase_SOURCE__.starts_with("pub struct LowerCase"));
+    assert!(cased::__KeepAsIs_SOURCE__.starts_with("pub struct KeepAsIs"));
+    assert!(cased::__HTTP_SERVER_CONFIG_SOURCE__.starts_with("pub struct HTTPServerConfig"));
+    assert!(cased::__PARSE_V2_HEADER_SOURCE__.starts_with("pub fn parse_v2_header"));
+    assert!(cased::__CUSTOM_NAME_SOURCE__.starts_with("pub struct Renamed"));
+    assert!(cased::full_path::__FOO_BAR_BAZ_SOURCE__.contains("fn screaming"));
+}
+
+#[test]
+fn case_applies_to_metadata_constants() {
+    assert_eq!(cased::__lowercase_EMBED_OPTS__, "case = \"lower\"");
+    assert!(cased::__PARSE_V2_HEADER_QUALIFIERS__.is_empty());
+}
+
+pub mod raw_identifiers {
+    use src_embed::src_embed;
+
+    #[src_embed]
+    pub fn r#type() -> u8 {
+        1
+    }
+}
+
+#[test]
+fn raw_identifiers_lose_their_prefix() {
+    assert_eq!(raw_identifiers::r#type(), 1);
+    assert!(raw_identifiers::__TYPE_SOURCE__.starts_with("pub fn r#type"));
+}
+
+#[test]
+fn unknown_case_is_an_error() {
+    let expanded = src_embed_core::expand(
+        "case = \"camel\"".parse().unwrap(),
+        "pub fn camel() {}".parse().unwrap(),
+    )
+    .to_string();
+    assert!(expanded.contains("compile_error"), "{}", expanded);
+    assert!(
+        expanded.contains("expected `\\\"upper\\\"`, `\\\"lower\\\"`"),
+        "{}",
+        expanded
+    );
+}
+
+#[test]
+fn name_that_is_not_an_identifier_is_an_error() {
+    let expanded = src_embed_core::expand(
+        "name = \"two words\", case = \"preserve\"".parse().unwrap(),
+        "pub fn spaced() {}".parse().unwrap(),
+    )
+    .to_string();
+    assert!(expanded.contains("compile_error"), "{}", expanded);
+    assert!(
+        expanded.contains("`name` must be an identifier"),
+        "{}",
+        expanded
+    );
+}
@@ -0,0 +1,60 @@
+//! `nested` also embeds the items declared inside a function.
+
+use src_embed::src_embed;
+
+#[src_embed(nested)]
+pub fn outer(values: &[u32]) -> u32 {
+    fn double(value: u32) -> u32 {
+        value * 2
+    }
+
+    values.iter().map(|value| double(*value)).sum()
+}
+
+// Written with the helper on one line on purpose.
+#[rustfmt::skip]
+mod one_line {
+    use src_embed::src_embed;
+
+    #[src_embed(nested, raw)]
+    pub fn tutorial() -> u32 {
+        // A helper shown in the tutorial.
+        struct Step { size: u32 }
+
+        let step = Step { size: 3 };
+        step.size
+    }
+}
+
+use one_line::{tutorial, __TUTORIAL_STEP_EMBED_OPTS__, __TUTORIAL_STEP_SOURCE__};
+
+#[src_embed(nested)]
+pub fn plain() -> u32 {
+    7
+}
+
+#[test]
+fn nested_items_are_embedded_with_the_parent_name() {
+    assert_eq!(outer(&[1, 2]), 6);
+    assert_eq!(
+        __OUTER_DOUBLE_SOURCE__,
+        "fn double(value: u32) -> u32 { value * 2 }"
+    );
+    assert_eq!(__OUTER_DOUBLE_PARAMS__, ["value: u32"]);
+    assert!(__OUTER_SOURCE__.contains("fn double"));
+    assert_eq!(__OUTER_EMBED_OPTS__, "nested");
+}
+
+#[test]
+fn nested_items_use_the_parent_arguments() {
+    assert_eq!(tutorial(), 3);
+    assert_eq!(__TUTORIAL_STEP_SOURCE__, "struct Step { size: u32 }");
+    // The arguments are the function's, as given.
+    assert_eq!(__TUTORIAL_STEP_EMBED_OPTS__, "raw, nested");
+}
+
+#[test]
+fn functions_without_items_embed_only_themselves() {
+    assert_eq!(plain(), 7);
+    assert_eq!(__PLAIN_SOURCE__, "pub fn plain() -> u32 { 7 }");
+}
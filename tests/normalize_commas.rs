@@ -0,0 +1,139 @@
+//! `normalize_commas` embeds sources without trailing commas.
+
+// The items exist for their sources only.
+#![allow(dead_code)]
+
+#[rustfmt::skip]
+mod trailing {
+    use src_embed::src_embed;
+
+    #[src_embed(normalize_commas)]
+    pub struct Pair<A, B,> {
+        pub first: A,
+        pub second: B,
+    }
+}
+
+#[rustfmt::skip]
+mod bare {
+    use src_embed::src_embed;
+
+    #[src_embed(normalize_commas)]
+    pub struct Pair<A, B> {
+        pub first: A,
+        pub second: B
+    }
+}
+
+#[rustfmt::skip]
+mod pretty_trailing {
+    use src_embed::src_embed;
+
+    #[src_embed(pretty, normalize_commas)]
+    pub enum Axis { X, Y, }
+}
+
+#[rustfmt::skip]
+mod pretty_bare {
+    use src_embed::src_embed;
+
+    #[src_embed(pretty, normalize_commas)]
+    pub enum Axis { X, Y }
+}
+
+#[rustfmt::skip]
+mod raw_trailing {
+    use src_embed::src_embed;
+
+    #[src_embed(raw, normalize_commas)]
+    pub fn sum(
+        a: u8, // first
+        b: u8, /* second */
+    ) -> [u8; 2] {
+        [a, b,]
+    }
+}
+
+#[rustfmt::skip]
+mod raw_bare {
+    use src_embed::src_embed;
+
+    #[src_embed(raw, normalize_commas)]
+    pub fn sum(
+        a: u8 // first
+        , b: u8 /* second */
+    ) -> [u8; 2] {
+        [a, b]
+    }
+}
+
+#[rustfmt::skip]
+mod raw_literals {
+    use src_embed::src_embed;
+
+    #[src_embed(raw, normalize_commas)]
+    pub fn literals() -> (&'static str, char, &'static str) {
+        let _ = 'a';
+        (", )", ',', r#"[1, 2,]"#,)
+    }
+}
+
+#[rustfmt::skip]
+mod tuples {
+    use src_embed::src_embed;
+
+    #[src_embed(normalize_commas)]
+    pub struct One((u8,), [u8; 1]);
+
+    #[src_embed(raw, normalize_commas)]
+    pub fn single() -> ((u8,), (u8, u8)) {
+        let t = (1,);
+        (t, (2, 3,))
+    }
+}
+
+#[test]
+fn comma_styles_embed_the_same_source() {
+    assert_eq!(trailing::__PAIR_SOURCE__, bare::__PAIR_SOURCE__);
+    assert!(!trailing::__PAIR_SOURCE__.contains(", }"));
+    assert_eq!(trailing::__PAIR_EMBED_OPTS__, "normalize_commas");
+    assert_eq!(
+        pretty_trailing::__AXIS_SOURCE__,
+        "pub enum Axis {\n    X,\n    Y\n}"
+    );
+    assert_eq!(
+        pretty_trailing::__AXIS_SOURCE__,
+        pretty_bare::__AXIS_SOURCE__
+    );
+}
+
+#[test]
+fn raw_captures_keep_layout_and_comments() {
+    assert_eq!(raw_trailing::sum(1, 2), [1, 2]);
+    assert_eq!(
+        raw_trailing::__SUM_SOURCE__,
+        "pub fn sum(\n        a: u8, // first\n        b: u8 /* second */\n    \
+         ) -> [u8; 2] {\n        [a, b]\n    }"
+    );
+    assert_eq!(raw_bare::sum(1, 2), [1, 2]);
+}
+
+#[test]
+fn literals_keep_their_commas() {
+    assert_eq!(raw_literals::literals(), (", )", ',', "[1, 2,]"));
+    assert_eq!(
+        raw_literals::__LITERALS_SOURCE__,
+        "pub fn literals() -> (&'static str, char, &'static str) {\n        let _ = 'a';\n        \
+         (\", )\", ',', r#\"[1, 2,]\"#)\n    }"
+    );
+}
+
+#[test]
+fn one_element_tuples_keep_their_comma() {
+    assert_eq!(tuples::single(), ((1,), (2, 3)));
+    assert_eq!(tuples::__ONE_SOURCE__, "pub struct One((u8,), [u8; 1]);");
+    assert_eq!(
+        tuples::__SINGLE_SOURCE__,
+        "pub fn single() -> ((u8,), (u8, u8)) {\n        let t = (1,);\n        (t, (2, 3))\n    }"
+    );
+}
@@ -0,0 +1,77 @@
+//! The embedded source does not depend on where `#[src_embed]` sits among
+//! the item's other attributes, derives and attribute macros included.
+
+use src_embed::src_embed;
+
+#[derive(Debug, Clone)]
+#[src_embed]
+pub struct DeriveAbove;
+
+#[src_embed]
+#[derive(Debug, Clone)]
+pub struct DeriveBelow;
+
+#[derive(Debug)]
+#[src_embed(raw)]
+#[repr(C)]
+pub struct RawAbove {
+    pub x: u8,
+}
+
+#[repr(C)]
+#[src_embed(raw)]
+#[derive(Debug)]
+pub struct RawBelow {
+    pub x: u8,
+}
+
+#[test]
+#[src_embed]
+fn test_above() {}
+
+#[src_embed]
+#[test]
+fn test_below() {}
+
+#[src_embed(name = "OUTER")]
+#[src_embed::src_embed(name = "INNER")]
+pub struct Twice;
+
+#[test]
+fn derives_are_embedded_in_either_order() {
+    let _ = (DeriveAbove.clone(), DeriveBelow.clone());
+    assert_eq!(
+        __DERIVEABOVE_SOURCE__,
+        "#[derive(Debug, Clone)] pub struct DeriveAbove;"
+    );
+    assert_eq!(
+        __DERIVEBELOW_SOURCE__,
+        "#[derive(Debug, Clone)] pub struct DeriveBelow;"
+    );
+}
+
+#[test]
+fn raw_keeps_the_written_order() {
+    let _ = (RawAbove { x: 0 }, RawBelow { x: 0 });
+    assert_eq!(
+        __RAWABOVE_SOURCE__,
+        "#[derive(Debug)]\n#[repr(C)]\npub struct RawAbove {\n    pub x: u8,\n}"
+    );
+    assert_eq!(
+        __RAWBELOW_SOURCE__,
+        "#[repr(C)]\n#[derive(Debug)]\npub struct RawBelow {\n    pub x: u8,\n}"
+    );
+}
+
+#[test]
+fn other_attribute_macros_are_embedded_in_either_order() {
+    assert_eq!(__TEST_ABOVE_SOURCE__, "#[test] fn test_above() {}");
+    assert_eq!(__TEST_BELOW_SOURCE__, "#[test] fn test_below() {}");
+}
+
+#[test]
+fn stacked_src_embed_attributes_are_never_embedded() {
+    let _ = Twice;
+    assert_eq!(__OUTER_SOURCE__, "pub struct Twice;");
+    assert_eq!(__INNER_SOURCE__, "pub struct Twice;");
+}
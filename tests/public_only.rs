@@ -0,0 +1,85 @@
+//! `public_only` embeds only the public items of an `impl` block.
+
+use src_embed::src_embed;
+
+pub struct Counter(u32);
+
+#[src_embed(public_only)]
+impl Counter {
+    pub const START: u32 = 0;
+
+    /// Creates a counter.
+    pub fn zero() -> Self {
+        Counter(Self::START)
+    }
+
+    pub fn tick(&mut self) -> u32 {
+        self.bump();
+        self.0
+    }
+
+    fn bump(&mut self) {
+        self.0 += 1;
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.0 = Self::START;
+    }
+}
+
+pub mod laid_out {
+    use src_embed::src_embed;
+
+    pub struct Gauge;
+
+    #[src_embed(public_only, pretty)]
+    impl Gauge {
+        pub fn read(&self) -> u8 {
+            self.raw()
+        }
+
+        fn raw(&self) -> u8 {
+            7
+        }
+    }
+}
+
+pub trait Named {
+    fn name(&self) -> &'static str;
+}
+
+#[src_embed(public_only, name = "NAMED_COUNTER")]
+impl Named for Counter {
+    fn name(&self) -> &'static str {
+        "counter"
+    }
+}
+
+#[test]
+fn inherent_impl_keeps_pub_items() {
+    let mut counter = Counter::zero();
+    assert_eq!(counter.tick(), 1);
+    counter.reset();
+    assert_eq!(counter.tick(), 1);
+    assert_eq!(
+        __COUNTER_SOURCE__,
+        "impl Counter { pub const START : u32 = 0; /// Creates a counter.\n\
+         pub fn zero() -> Self { Counter(Self::START) } pub fn tick(&mut self) -> u32 \
+         { self.bump(); self.0 } }"
+    );
+}
+
+#[test]
+fn pretty_lays_out_the_public_items() {
+    assert_eq!(laid_out::Gauge.read(), 7);
+    assert_eq!(
+        laid_out::__GAUGE_SOURCE__,
+        "impl Gauge {\n    pub fn read(&self) -> u8 {\n        self.raw()\n    }\n}"
+    );
+}
+
+#[test]
+fn trait_impl_is_embedded_whole() {
+    assert_eq!(Counter::zero().name(), "counter");
+    assert!(__NAMED_COUNTER_SOURCE__.contains("fn name"));
+}
@@ -0,0 +1,85 @@
+//! Round-trip integration test for embedded sources.
+//!
+//! The constants generated by `#[src_embed]` promise to hold the item as it
+//! was written. This test collects several embedded sources, writes them
+//! together into a temporary `.rs` file and parses the file back with `syn`
+//! to confirm the combination still forms a valid sequence of Rust items.
+
+use std::fs;
+
+use src_embed::src_embed;
+
+/// A generic container with a where-clause.
+#[src_embed]
+#[derive(Debug, Clone)]
+pub struct Container<T>
+where
+    T: Clone,
+{
+    pub items: Vec<T>,
+    pub label: &'static str,
+}
+
+#[src_embed]
+pub enum Shape {
+    Circle { radius: f64 },
+    Rect(f64, f64),
+    Empty,
+}
+
+#[src_embed]
+pub trait Area {
+    fn area(&self) -> f64;
+
+    fn is_empty(&self) -> bool {
+        self.area() == 0.0
+    }
+}
+
+// The impl derives the same constant name as `Shape`, so it lives in its own
+// module.
+mod shape_area {
+    use super::*;
+
+    #[src_embed]
+    impl Area for Shape {
+        fn area(&self) -> f64 {
+            match self {
+                Shape::Circle { radius } => std::f64::consts::PI * radius * radius,
+                Shape::Rect(w, h) => w * h,
+                Shape::Empty => 0.0,
+            }
+        }
+    }
+}
+
+#[src_embed]
+pub fn largest<T: PartialOrd>(items: &[T]) -> Option<&T> {
+    let mut iter = items.iter();
+    let first = iter.next()?;
+    Some(iter.fold(first, |best, item| if item > best { item } else { best }))
+}
+
+#[test]
+fn embedded_sources_parse_back_as_items() {
+    let sources = [
+        __CONTAINER_SOURCE__,
+        __SHAPE_SOURCE__,
+        __AREA_SOURCE__,
+        shape_area::__SHAPE_SOURCE__,
+        __LARGEST_SOURCE__,
+    ];
+
+    let path = std::env::temp_dir().join(format!("src_embed_round_trip_{}.rs", std::process::id()));
+    fs::write(&path, sources.join("\n\n")).expect("write temporary source file");
+    let text = fs::read_to_string(&path).expect("read temporary source file");
+    fs::remove_file(&path).ok();
+
+    let file = syn::parse_file(&text).expect("embedded sources form valid items");
+    assert_eq!(file.items.len(), sources.len());
+    assert!(matches!(&file.items[0], syn::Item::Struct(item) if item.ident == "Container"));
+    assert!(matches!(&file.items[1], syn::Item::Enum(item) if item.ident == "Shape"));
+    assert!(matches!(&file.items[2], syn::Item::Trait(item) if item.ident == "Area"));
+    assert!(matches!(&file.items[3], syn::Item::Impl(item) if item.trait_.is_some()));
+    assert!(matches!(&file.items[4], syn::Item::Fn(item) if item.sig.ident == "largest"));
+}
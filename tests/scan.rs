@@ -0,0 +1,160 @@
+//! `scan_source` predicts the constants `#[src_embed]` generates.
+
+use quote::ToTokens;
+use src_embed::{scan_source, src_embed};
+
+const SOURCE: &str = r#"
+    #[src_embed]
+    pub struct Foo;
+
+    #[derive(Debug)]
+    #[src_embed]
+    pub enum Kind { A, B }
+
+    pub fn not_embedded() {}
+
+    pub mod nested {
+        #[src_embed::src_embed]
+        pub trait Marker {}
+
+        #[src_embed(full_path)]
+        impl crate::other::Thing {}
+    }
+"#;
+
+#[test]
+fn finds_annotated_items_and_names() {
+    let names: Vec<_> = scan_source(SOURCE)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "__FOO_SOURCE__",
+            "__KIND_SOURCE__",
+            "__MARKER_SOURCE__",
+            "__CRATE_OTHER_THING_SOURCE__",
+        ]
+    );
+}
+
+#[test]
+fn source_keeps_other_attributes_but_not_src_embed() {
+    let found = scan_source(SOURCE);
+    let (_, kind) = &found[1];
+    assert!(kind.contains("derive"));
+    assert!(!kind.contains("src_embed"));
+}
+
+#[test]
+fn grouping_modules_yield_their_items() {
+    let found = scan_source("#[src_embed(group_by_kind)] mod catalog { #[src_embed] struct Foo; }");
+    let names: Vec<_> = found.into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, ["__FOO_SOURCE__"]);
+}
+
+#[test]
+fn invalid_text_yields_nothing() {
+    assert!(scan_source("pub struct").is_empty());
+    assert!(scan_source("#[src_embed(bogus)] pub struct Foo;").is_empty());
+}
+
+#[src_embed]
+pub fn matches_macro(x: u32) -> u32 {
+    x + 1
+}
+
+#[test]
+fn prediction_matches_macro_output() {
+    let text = "#[src_embed]\npub fn matches_macro(x: u32) -> u32 {\n    x + 1\n}\n";
+    let found = scan_source(text);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].0, "__MATCHES_MACRO_SOURCE__");
+
+    // Whitespace may differ, so compare the token streams.
+    let tokens = |source: &str| {
+        syn::parse_str::<syn::Item>(source)
+            .expect("valid item")
+            .to_token_stream()
+            .to_string()
+    };
+    assert_eq!(tokens(&found[0].1), tokens(__MATCHES_MACRO_SOURCE__));
+}
+
+#[test]
+fn prediction_honours_name() {
+    let found = scan_source("#[src_embed(name = \"Renamed\", full_path)] impl a::B {}");
+    assert_eq!(found[0].0, "__RENAMED_SOURCE__");
+}
+
+#[test]
+fn finds_items_of_src_embed_all_blocks() {
+    let text = r#"
+        src_embed_all! {
+            #![dedup]
+            pub struct Foo;
+            #[src_embed(name = "Other")]
+            pub fn bar() {}
+        }
+    "#;
+    let names: Vec<_> = scan_source(text)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(names, ["__FOO_SOURCE__", "__OTHER_SOURCE__"]);
+}
+
+#[test]
+fn prediction_honours_case() {
+    let found = scan_source("#[src_embed(case = \"screaming_snake\")] pub struct FooBar;");
+    assert_eq!(found[0].0, "__FOO_BAR_SOURCE__");
+}
+
+#[test]
+fn finds_items_of_src_embed_trait_impls_blocks() {
+    let text = r#"
+        src_embed_trait_impls! {
+            pub trait Shape { fn sides(&self) -> u32; }
+            impl Shape for Circle { fn sides(&self) -> u32 { 0 } }
+            #[src_embed(name = "Boxed")]
+            impl<T: Shape> Shape for Box<T> { fn sides(&self) -> u32 { 1 } }
+        }
+    "#;
+    let names: Vec<_> = scan_source(text)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "__SHAPE_SOURCE__",
+            "__SHAPE_FOR_CIRCLE_SOURCE__",
+            "__BOXED_SOURCE__"
+        ]
+    );
+}
+
+#[test]
+fn finds_nested_items() {
+    let text = r#"
+        #[src_embed(nested)]
+        pub fn outer() -> u32 {
+            fn double(value: u32) -> u32 { value * 2 }
+            struct Step;
+            double(1)
+        }
+    "#;
+    let names: Vec<_> = scan_source(text)
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "__OUTER_SOURCE__",
+            "__OUTER_DOUBLE_SOURCE__",
+            "__OUTER_STEP_SOURCE__"
+        ]
+    );
+}
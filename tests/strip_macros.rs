@@ -0,0 +1,42 @@
+//! `strip_macros` leaves the bodies of macro invocations out of the
+//! embedded source.
+
+use src_embed::src_embed;
+
+#[src_embed(strip_macros)]
+pub fn report(values: &[u32]) -> String {
+    let doubled: Vec<u32> = values.iter().map(|value| value * 2).collect();
+    assert!(!doubled.is_empty(), "no values");
+    format!("{:?}", doubled)
+}
+
+#[src_embed(strip_macros, pretty)]
+pub fn listed() -> Vec<u8> {
+    let mut list = vec![1, 2, 3];
+    list.push(4);
+    list
+}
+
+#[test]
+fn macro_bodies_become_placeholders() {
+    assert_eq!(report(&[1, 2]), "[2, 4]");
+    // Outside `pretty`, the compiler decides the layout of the rebuilt block.
+    let source: String = __REPORT_SOURCE__.split_whitespace().collect();
+    assert_eq!(
+        source,
+        "pubfnreport(values:&[u32])->String{letdoubled:Vec<u32>=\
+         values.iter().map(|value|value*2).collect();assert!(/*macro*/);format!(/*macro*/)}"
+    );
+    assert!(!__REPORT_SOURCE__.contains("no values"));
+    assert_eq!(__REPORT_EMBED_OPTS__, "strip_macros");
+}
+
+#[test]
+fn pretty_layout_keeps_the_structure() {
+    assert_eq!(listed(), [1, 2, 3, 4]);
+    assert_eq!(
+        __LISTED_SOURCE__,
+        "pub fn listed() -> Vec<u8> {\n    let mut list = vec![/* macro */];\n    \
+         list.push(4);\n    list\n}"
+    );
+}
@@ -0,0 +1,60 @@
+//! `sync_check = "path"` fails the build when the embedded source differs
+//! from a file, such as a snippet the docs include.
+
+use src_embed::src_embed;
+
+/// The snippet as the docs show it:
+///
+/// ```
+#[doc = include_str!("fixtures/greet.rs")]
+/// ```
+pub mod docs {}
+
+#[src_embed(raw, sync_check = "tests/fixtures/greet.rs")]
+pub fn greet(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+
+#[test]
+fn matching_source_compiles() {
+    assert_eq!(greet("Ada"), "Hello, Ada!");
+    assert_eq!(
+        format!("{}\n", __GREET_SOURCE__),
+        include_str!("fixtures/greet.rs")
+    );
+    assert_eq!(
+        __GREET_EMBED_OPTS__,
+        "sync_check = \"tests/fixtures/greet.rs\", raw"
+    );
+}
+
+#[test]
+fn mismatch_names_the_first_differing_line() {
+    // Outside the compiler spans have no source text, so `raw` would not apply.
+    let item = "pub fn greet(name: &str) -> String {\n    format!(\"Hi, {}!\", name)\n}";
+    let expanded = src_embed_core::expand(
+        "pretty, sync_check = \"tests/fixtures/greet.rs\""
+            .parse()
+            .unwrap(),
+        item.parse().unwrap(),
+    )
+    .to_string();
+    assert!(expanded.contains("compile_error"), "{}", expanded);
+    assert!(
+        expanded.contains("differs from `tests/fixtures/greet.rs` at line 2"),
+        "{}",
+        expanded
+    );
+}
+
+#[test]
+fn missing_file_is_an_error() {
+    let expanded = src_embed_core::expand(
+        "sync_check = \"tests/fixtures/missing.rs\""
+            .parse()
+            .unwrap(),
+        "pub struct Missing;".parse().unwrap(),
+    )
+    .to_string();
+    assert!(expanded.contains("cannot read"), "{}", expanded);
+}
@@ -0,0 +1,66 @@
+//! `tabs = N` expands the leading tabs of a raw capture to spaces.
+
+// Indented with tabs on purpose.
+#[rustfmt::skip]
+mod indented {
+	use src_embed::src_embed;
+
+	#[src_embed(raw, tabs = 4)]
+	pub fn tabbed() -> &'static str {
+		// a	comment
+	  	"keep	this"
+	}
+
+	#[src_embed(raw, reindent, tabs = 2)]
+	pub fn shifted() -> u8 {
+		1
+	}
+
+	#[src_embed(raw)]
+	pub fn kept() -> u8 {
+		2
+	}
+
+	#[src_embed(raw, tabs = 4)]
+	pub fn literal() -> &'static str {
+		"line
+	continued"
+	}
+}
+
+#[test]
+fn leading_tabs_expand_to_tab_stops() {
+    assert_eq!(indented::tabbed(), "keep\tthis");
+    assert_eq!(
+        indented::__TABBED_SOURCE__,
+        "pub fn tabbed() -> &'static str {\n        // a\tcomment\n        \"keep\tthis\"\n    }"
+    );
+    assert_eq!(indented::__TABBED_EMBED_OPTS__, "tabs = 4, raw");
+}
+
+#[test]
+fn tabs_apply_after_reindent() {
+    assert_eq!(indented::shifted(), 1);
+    assert_eq!(
+        indented::__SHIFTED_SOURCE__,
+        "pub fn shifted() -> u8 {\n  1\n}"
+    );
+}
+
+#[test]
+fn tabs_are_kept_by_default() {
+    assert_eq!(indented::kept(), 2);
+    assert_eq!(
+        indented::__KEPT_SOURCE__,
+        "pub fn kept() -> u8 {\n\t\t2\n\t}"
+    );
+}
+
+#[test]
+fn multi_line_literals_are_left_alone() {
+    assert_eq!(indented::literal(), "line\n\tcontinued");
+    assert_eq!(
+        indented::__LITERAL_SOURCE__,
+        "pub fn literal() -> &'static str {\n\t\t\"line\n\tcontinued\"\n\t}"
+    );
+}
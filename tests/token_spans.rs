@@ -0,0 +1,74 @@
+//! `__FOO_TOKEN_SPANS__` locates each token in the embedded source.
+#![cfg(feature = "token-spans")]
+
+use src_embed::src_embed;
+
+#[src_embed]
+pub fn add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[src_embed(raw)]
+/// Documented.
+pub struct Pair(
+    // A comment between tokens.
+    pub u8,
+    pub u8,
+);
+
+fn tokens(source: &str, spans: &[(usize, usize)]) -> Vec<String> {
+    spans
+        .iter()
+        .map(|&(start, end)| source[start..end].to_string())
+        .collect()
+}
+
+#[test]
+fn spans_cover_each_token_in_order() {
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(
+        tokens(__ADD_SOURCE__, __ADD_TOKEN_SPANS__),
+        [
+            "pub", "fn", "add", "(", "a", ":", "u32", ",", "b", ":", "u32", ")", "-", ">", "u32",
+            "{", "a", "+", "b", "}"
+        ]
+    );
+}
+
+#[test]
+fn spans_follow_the_raw_text() {
+    assert_eq!(Pair(1, 2).1, 2);
+    let found = tokens(__PAIR_SOURCE__, __PAIR_TOKEN_SPANS__);
+    assert_eq!(found[0], "/// Documented.");
+    assert_eq!(
+        found[1..],
+        ["pub", "struct", "Pair", "(", "pub", "u8", ",", "pub", "u8", ",", ")", ";"]
+    );
+    let (start, _) = __PAIR_TOKEN_SPANS__[5];
+    assert!(start > __PAIR_SOURCE__.find("comment").unwrap());
+}
+
+#[src_embed(normalize_commas, anonymize, header = "checksum")]
+pub fn scale(values: &[f32], by: f32) -> Vec<f32> {
+    values
+        .iter()
+        .map(|value| value * by * 2.5e-3)
+        .collect::<Vec<_>>()
+}
+
+#[test]
+fn spans_follow_the_rewritten_text() {
+    assert_eq!(scale(&[1000.0], 2.0), [5.0]);
+    // The header line is a plain comment, and the ranges hold the renamed
+    // identifiers.
+    assert!(__SCALE_SOURCE__.starts_with("// sha: "));
+    assert_eq!(
+        tokens(__SCALE_SOURCE__, __SCALE_TOKEN_SPANS__),
+        [
+            "pub", "fn", "fn_a", "(", "var_b", ":", "&", "[", "f32", "]", ",", "var_c", ":", "f32",
+            ")", "-", ">", "Vec", "<", "f32", ">", "{", "var_b", ".", "iter", "(", ")", ".", "map",
+            "(", "|", "var_d", "|", "var_d", "*", "var_c", "*", "2.5e-3", ")", ".", "collect", ":",
+            ":", "<", "Vec", "<", "_", ">", ">", "(", ")", "}"
+        ]
+    );
+}
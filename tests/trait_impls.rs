@@ -0,0 +1,62 @@
+//! `src_embed_trait_impls!` embeds a trait and each of its impls.
+
+use src_embed::src_embed_trait_impls;
+
+pub struct Circle;
+pub mod shapes {
+    pub struct Square;
+}
+
+src_embed_trait_impls! {
+    /// Something with corners.
+    pub trait Shape {
+        fn sides(&self) -> u32;
+    }
+
+    impl Shape for Circle {
+        fn sides(&self) -> u32 { 0 }
+    }
+
+    #[src_embed(full_path)]
+    impl Shape for shapes::Square {
+        fn sides(&self) -> u32 { 4 }
+    }
+
+    #[src_embed(name = "Boxed")]
+    impl<T: Shape> Shape for Box<T> {
+        fn sides(&self) -> u32 { (**self).sides() }
+    }
+}
+
+#[test]
+fn embeds_the_trait() {
+    assert_eq!(
+        __SHAPE_SOURCE__,
+        "/// Something with corners.\npub trait Shape { fn sides(&self) -> u32; }"
+    );
+}
+
+#[test]
+fn names_impls_after_the_trait_and_type() {
+    assert_eq!(Circle.sides() + shapes::Square.sides(), 4);
+    assert_eq!(
+        __SHAPE_FOR_CIRCLE_SOURCE__,
+        "impl Shape for Circle { fn sides(&self) -> u32 { 0 } }"
+    );
+    assert!(__SHAPE_FOR_SHAPES_SQUARE_SOURCE__.starts_with("impl Shape for shapes"));
+    assert!(__SHAPE_FOR_CIRCLE_ASSOC_CONSTS__.is_empty());
+}
+
+#[test]
+fn embed_opts_list_only_the_arguments_given() {
+    assert_eq!(__SHAPE_FOR_CIRCLE_EMBED_OPTS__, "");
+    assert_eq!(__SHAPE_FOR_SHAPES_SQUARE_EMBED_OPTS__, "full_path");
+    assert_eq!(__BOXED_EMBED_OPTS__, "name = \"Boxed\"");
+}
+
+#[test]
+fn own_names_win() {
+    assert_eq!(Box::new(Circle).sides(), 0);
+    let source: String = __BOXED_SOURCE__.split_whitespace().collect();
+    assert!(source.starts_with("impl<T:Shape>ShapeforBox<T>"));
+}